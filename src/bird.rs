@@ -1,11 +1,32 @@
-use bevy::{ecs::resource::Resource, mesh::Mesh};
-use csgrs::{mesh::plane::Plane, traits::CSG};
-type CSGMesh = csgrs::mesh::Mesh<()>;
+use bevy::{
+    asset::RenderAssetUsages,
+    color::Color,
+    ecs::resource::Resource,
+    math::{DQuat, DVec3},
+    mesh::{Mesh, PrimitiveTopology},
+};
+use csgrs::{
+    mesh::{plane::Plane, polygon::Polygon, vertex::Vertex},
+    sketch::Sketch,
+    traits::CSG,
+};
+use serde::{Deserialize, Serialize};
+pub type CSGMesh = csgrs::mesh::Mesh<()>;
 use bevy::log::info;
 
+use crate::{
+    relief::make_relief,
+    remesh::IndexedMesh,
+    sdf::Sdf,
+    stats::MeshStats,
+    voxel::{VoxelBird, voxelize},
+};
+
 // Inputs/descriptions copied from original Bird-o-matic .SCAD script (see referenced script at bottom of file)
 // [Ed. note: Made em all f32's for now]
-#[derive(Resource, Clone, Copy)]
+// Saved designs are just this struct as JSON, missing fields fall back to the defaults
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BirdGenInputs {
     // Length of the beak
     pub beak_length: f32, // [0:50]
@@ -15,6 +36,12 @@ pub struct BirdGenInputs {
     pub beak_width: f32, // [0:25]
     // Shape of the beak tip (lowest is flat)
     pub beak_roundness: f32, // [10:200]
+    // Straight cone, hooked (parrot/raptor), curved (curlew/hummingbird) or flat duck bill
+    pub beak_shape: BeakShape,
+    // How much the beak bends down along its length, in degrees (negative bends up)
+    pub beak_curve: f32, // [-45:90]
+    // How far the tip hooks down at the very end
+    pub beak_droop: f32, // [0:100]
 
     // Head diameter
     pub head_size: f32, // [10:40]
@@ -22,6 +49,14 @@ pub struct BirdGenInputs {
     pub head_to_belly: f32, // [-20:50]
     // Size of the eyes
     pub eye_size: f32, // [0:20]
+    // Bumps, sockets for beads/googly eyes, or domes with separate pupils
+    pub eye_style: EyeStyle,
+    // How far the eyes sit apart, around the skull
+    pub eye_spacing: f32, // [0:90]
+    // How far forward the eyes look
+    pub eye_angle: f32, // [-30:90]
+    // Diameter of the bead (or googly eye) the eye sockets are made for
+    pub eye_bead_size: f32, // [2:16]
     // Head lateral offset
     pub head_lateral_offset: f32, // [-15:15]
     // Head vertical height
@@ -30,6 +65,18 @@ pub struct BirdGenInputs {
     pub head_yaw: f32, // [-45:45]
     // Head vertical rotation (positive is upwards)
     pub head_pitch: f32, // [-80:45]
+    // Radius of the smooth blend where the neck meets the head (0 for none).
+    // The head becomes part of the body (and its colour), unless it's split off
+    pub neck_fillet: f32, // [0:20]
+
+    // Crest height (0 for no crest)
+    pub crest_height: f32, // [0:40]
+    // Number of plumes in the crest
+    pub crest_plumes: f32, // [1:9]
+    // How far back the crest leans (0 is straight up)
+    pub crest_sweep: f32, // [-30:90]
+    // Thickness of each plume
+    pub crest_thickness: f32, // [1:8]
 
     // How long is the front body
     pub belly_length: f32, // [10:100]
@@ -42,6 +89,29 @@ pub struct BirdGenInputs {
     pub belly_to_bottom: f32, // [1:50]
     // Bottom diameter
     pub bottom_size: f32, // [5:50]
+    // Hull of spheres (the original), or cross sections lofted along a spine from neck to tail
+    pub body_style: BodyStyle,
+    // Lofted body only: how far the neck runs out of the head before the chest starts
+    pub neck_length: f32, // [0:40]
+    // Lofted body only: how far the belly hangs down below the round shape
+    pub belly_sag: f32, // [-50:50]
+    // Lofted body only: how much the back arches up (negative for a flat back)
+    pub back_arch: f32, // [-50:50]
+    // How the sphere hull body gets built: csgrs hulls, or distance fields blended together
+    pub geometry: GeometryBackend,
+    // SDF only: how wide the fillets are where the head, neck, chest, bottom and tail blend together
+    pub blend_radius: f32, // [0:30]
+
+    // Wing length (0 for no wings)
+    pub wing_length: f32, // [0:80]
+    // Wing width at the shoulder
+    pub wing_width: f32, // [5:40]
+    // How much the wing tips hang down
+    pub wing_droop: f32, // [-30:60]
+    // How far the wings swing out from the body
+    pub wing_spread: f32, // [0:90]
+    // Folded against the body, or spread out flat
+    pub wing_pose: WingPose,
 
     // Tail length
     pub tail_length: f32, //[0:100]
@@ -53,9 +123,438 @@ pub struct BirdGenInputs {
     pub tail_pitch: f32, // [-45:90]
     // How round is the tail (lowest is flat)
     pub tail_roundness: f32, // [10:200]
+    // Plain disc, swallow fork, fan of feathers, long point or a cocked up wren tail
+    pub tail_style: TailStyle,
+    // Number of feathers in the fan
+    pub tail_feathers: f32, // [3:12]
+    // Angle the fan (or the fork) spreads over
+    pub tail_spread: f32, // [10:180]
+    // How deep the fork notch goes, relative to the tail length
+    pub tail_fork: f32, // [10:90]
+    // How much longer the pointed tail gets
+    pub tail_point: f32, // [0:200]
+    // How upright the wren tail sticks (replaces the tail pitch)
+    pub tail_cock: f32, // [45:120]
+
+    // Size of the patch on the front of the chest (0 for none)
+    pub breast_patch: f32, // [0:60]
+    // Number of stripes across each wing
+    pub wing_bars: f32, // [0:4]
+    // Size of the spots below the eyes (0 for none)
+    pub cheek_spots: f32, // [0:15]
+    // Height of the band running through the eyes to the beak (0 for none)
+    pub face_mask: f32, // [0:15]
 
     // How to cut the base of the object (-1 to disable, then use your own slicer options)
     pub base_flat: f32, // [-100:100]
+
+    // What the bird stands on (legs and perch replace the flat base cut)
+    pub stand: BirdStand,
+    // Leg length, from the belly down to the feet
+    pub leg_length: f32, // [5:60]
+    // Leg diameter (toes are as thick as the legs)
+    pub leg_thickness: f32, // [2:10]
+    // Distance between the two legs
+    pub leg_spacing: f32, // [4:40]
+    // Number of toes on each foot
+    pub toe_count: f32, // [1:5]
+    // Angle the toes fan out over
+    pub toe_splay: f32, // [0:120]
+    // Diameter of the perch branch
+    pub perch_diameter: f32, // [4:20]
+    // Height of the post between the branch and the display base
+    pub perch_height: f32, // [0:60]
+
+    // Text on the name plate (empty for none)
+    pub name_text: String,
+    // Where the text goes, engraved under the base or on the front of a pedestal
+    pub name_plate: NamePlate,
+    // Cut into the pedestal or sticking out of it (the base always gets engraved)
+    pub name_style: NameStyle,
+    // Font for the text, from assets/fonts
+    pub name_font: NameFont,
+    // Height of the letters
+    pub name_size: f32, // [3:20]
+    // How deep the letters go (or how far they stick out)
+    pub name_depth: f32, // [0.2:3]
+
+    // Diameter of the hanging loop on the back (0 for none)
+    pub loop_diameter: f32, // [0:20]
+    // Where the loop sits along the back, from the neck (0) to the tail (100)
+    pub loop_position: f32, // [0:100]
+    // Pocket in the flat base for a disc magnet
+    pub magnet_size: MagnetSize,
+    // Screw diameter for the countersunk mounting hole up through the flat base (0 for none)
+    pub screw_diameter: f32, // [0:6]
+    // How much bird there is under the screw head
+    pub screw_depth: f32, // [2:20]
+
+    // Hollow the body out into a planter or pen cup (the head stays solid)
+    pub container: ContainerShape,
+    // Thinnest the walls around the cavity get
+    pub container_wall: f32, // [1:10]
+    // Diameter of the opening in the back (and of the cavity itself for the cylinder)
+    pub container_opening: f32, // [5:60]
+    // Drainage hole in the bottom of the cavity (0 for none)
+    pub drainage_diameter: f32, // [0:15]
+
+    // Carve a working whistle into the body, blown through a mouthpiece out the back by the tail
+    // (not with the container, the bird is already hollow then)
+    pub whistle: bool,
+    // How much of the belly the resonant chamber takes up (bigger chamber, lower note)
+    pub whistle_chamber: f32, // [10:60]
+    // Width of the windway slot (and the window it blows across)
+    pub windway_width: f32, // [3:10]
+
+    // Loop subdivision passes over the finished parts (each one is 4 times the triangles)
+    pub subdivisions: f32, // [0:2]
+    // Taubin smoothing passes over the finished parts
+    pub smoothing_passes: f32, // [0:20]
+    // Edges sharper than this stay sharp in the shading (0 for flat shading)
+    pub smoothing_angle: f32, // [0:90]
+    // Faceted low poly look: decimate the finished bird down and flat shade it
+    pub low_poly: bool,
+    // How many triangles the low poly bird ends up with (all the parts together)
+    pub low_poly_faces: f32, // [100:5000]
+    // Rebuild the finished bird out of cubes, or studded bricks, for block games and brick building
+    pub blocks: BlockStyle,
+    // Size of the blocks (the stud spacing for bricks)
+    pub voxel_size: f32, // [1:10]
+
+    // Which way to look at the bird for the SVG/DXF outline (and the relief)
+    pub outline_view: OutlineView,
+    // Export a cookie cutter wall around the outline instead of the outline itself
+    pub cookie_cutter: bool,
+    // Thickness of the cookie cutter wall
+    pub cutter_wall: f32, // [0.4:4]
+
+    // Which way to cut the bird into a stack of slices for laser cutting
+    pub slice_axis: SliceAxis,
+    // Thickness of the plywood/acrylic (a stack has one slice per sheet thickness)
+    pub sheet_thickness: f32, // [1:10]
+    // Interlocking grid of upright slices slotted together, instead of a stack
+    pub sliceform: bool,
+    // Gap between the sliceform slices
+    pub slice_spacing: f32, // [5:40]
+    // Size of the sheets the slices get laid out on
+    pub sheet_width: f32,  // [100:1000]
+    pub sheet_height: f32, // [100:1000]
+
+    // How many faces the paper model gets (fewer is easier to build)
+    pub paper_faces: f32, // [50:800]
+    // Size of the paper model, as a percentage of the printed bird
+    pub paper_scale: f32, // [50:400]
+    // Paper the nets get printed on
+    pub paper_size: PaperSize,
+
+    // Thickness of the mould block round the bird (also sizes the registration keys)
+    pub mould_wall: f32, // [5:30]
+    // Gap round the registration keys in their sockets, so the halves still close
+    pub key_clearance: f32, // [0:1]
+    // Diameter of the sprue the resin or chocolate gets poured in through
+    pub sprue_diameter: f32, // [3:15]
+    // How many air vents go up from the bird's high points
+    pub vent_count: f32, // [0:6]
+
+    // Flatten the bird into a relief facing up off the bed, for plaques, pendants and earrings
+    // (all in one piece, so one colour)
+    pub relief: bool,
+    // How deep the relief is, front to back
+    pub relief_depth: f32, // [1:20]
+    // Backing plate behind the relief, following its outline
+    pub relief_plate: bool,
+    // Thickness of the backing plate (and the jump ring loop)
+    pub plate_thickness: f32, // [0.8:5]
+    // How far the plate sticks out past the bird
+    pub plate_border: f32, // [0:10]
+    // Loop on top to hang it from
+    pub jump_ring: bool,
+    // Diameter of the hole for the jump ring
+    pub jump_ring_diameter: f32, // [1:6]
+
+    // Print the head separately, with an alignment peg and a matching socket in the neck
+    pub split_head: bool,
+    // Diameter of the alignment peg
+    pub peg_diameter: f32, // [2:15]
+    // How far the peg sticks out below the head
+    pub peg_length: f32, // [2:20]
+    // Extra room in the socket around the peg
+    pub peg_clearance: f32, // [0:1]
+
+    // Material density in g/cm^3 (PLA is about 1.24)
+    pub material_density: f32, // [0.8:2]
+    // Filament diameter
+    pub filament_diameter: FilamentDiameter,
+    // Layer height used for the print time estimate
+    pub layer_height: f32, // [0.08:0.4]
+    // Average print speed in mm/s
+    pub print_speed: f32, // [10:200]
+    // Infill percentage
+    pub infill: f32, // [0:100]
+
+    // Which part the colour sliders are editing (just UI state, not saved with the design)
+    #[serde(skip)]
+    pub colour_part: BirdPart,
+    pub body_colour: PartColour,
+    pub head_colour: PartColour,
+    pub beak_colour: PartColour,
+    pub eye_colour: PartColour,
+    pub pupil_colour: PartColour,
+    pub wing_colour: PartColour,
+    pub breast_patch_colour: PartColour,
+    pub wing_bar_colour: PartColour,
+    pub cheek_spot_colour: PartColour,
+    pub face_mask_colour: PartColour,
+}
+
+// Colour of one part of the bird, as HSL so the sliders feel nice
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PartColour {
+    pub hue: f32,        // [0:360]
+    pub saturation: f32, // [0:100]
+    pub lightness: f32,  // [0:100]
+}
+
+impl PartColour {
+    const fn hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        PartColour {
+            hue,
+            saturation,
+            lightness,
+        }
+    }
+
+    pub fn to_color(self) -> Color {
+        Color::hsl(self.hue, self.saturation / 100.0, self.lightness / 100.0)
+    }
+}
+
+// The separately coloured (and separately exported) pieces of the bird
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BirdPart {
+    Body,
+    Head,
+    Beak,
+    Eyes,
+    Pupils,
+    Wings,
+    // markings, painted onto the parts above
+    BreastPatch,
+    WingBars,
+    CheekSpots,
+    FaceMask,
+}
+
+impl BirdPart {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BirdPart::Body => "Body",
+            BirdPart::Head => "Head",
+            BirdPart::Beak => "Beak",
+            BirdPart::Eyes => "Eyes",
+            BirdPart::Pupils => "Pupils",
+            BirdPart::Wings => "Wings",
+            BirdPart::BreastPatch => "Breast Patch",
+            BirdPart::WingBars => "Wing Bars",
+            BirdPart::CheekSpots => "Cheek Spots",
+            BirdPart::FaceMask => "Face Mask",
+        }
+    }
+
+    // Whatever comes off with the head when it's printed on its own
+    pub fn on_head(&self) -> bool {
+        matches!(
+            self,
+            BirdPart::Head
+                | BirdPart::Beak
+                | BirdPart::Eyes
+                | BirdPart::Pupils
+                | BirdPart::CheekSpots
+                | BirdPart::FaceMask
+        )
+    }
+}
+
+pub fn part_colour(part: BirdPart, input_values: &BirdGenInputs) -> PartColour {
+    match part {
+        BirdPart::Body => input_values.body_colour,
+        BirdPart::Head => input_values.head_colour,
+        BirdPart::Beak => input_values.beak_colour,
+        BirdPart::Eyes => input_values.eye_colour,
+        BirdPart::Pupils => input_values.pupil_colour,
+        BirdPart::Wings => input_values.wing_colour,
+        BirdPart::BreastPatch => input_values.breast_patch_colour,
+        BirdPart::WingBars => input_values.wing_bar_colour,
+        BirdPart::CheekSpots => input_values.cheek_spot_colour,
+        BirdPart::FaceMask => input_values.face_mask_colour,
+    }
+}
+
+pub fn part_colour_mut(part: BirdPart, input_values: &mut BirdGenInputs) -> &mut PartColour {
+    match part {
+        BirdPart::Body => &mut input_values.body_colour,
+        BirdPart::Head => &mut input_values.head_colour,
+        BirdPart::Beak => &mut input_values.beak_colour,
+        BirdPart::Eyes => &mut input_values.eye_colour,
+        BirdPart::Pupils => &mut input_values.pupil_colour,
+        BirdPart::Wings => &mut input_values.wing_colour,
+        BirdPart::BreastPatch => &mut input_values.breast_patch_colour,
+        BirdPart::WingBars => &mut input_values.wing_bar_colour,
+        BirdPart::CheekSpots => &mut input_values.cheek_spot_colour,
+        BirdPart::FaceMask => &mut input_values.face_mask_colour,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BeakShape {
+    Straight,
+    Hooked,
+    Curved,
+    Duck,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TailStyle {
+    Classic,
+    Forked,
+    Fan,
+    Pointed,
+    Wren,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EyeStyle {
+    Bump,
+    Socket,
+    Domed,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BodyStyle {
+    Hull,
+    Lofted,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GeometryBackend {
+    Csg,
+    Sdf,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BlockStyle {
+    Off,
+    Voxels,
+    Bricks,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OutlineView {
+    Side,
+    Top,
+    Front,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SliceAxis {
+    Length,
+    Width,
+    Height,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PaperSize {
+    A4,
+    Letter,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WingPose {
+    Folded,
+    Spread,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BirdStand {
+    Belly,
+    Legs,
+    Perch,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NamePlate {
+    Off,
+    Base,
+    Pedestal,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NameStyle {
+    Engraved,
+    Embossed,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NameFont {
+    Brut,
+    Monument,
+}
+
+impl NameFont {
+    // baked in, so it works the same in the browser
+    pub fn bytes(&self) -> &'static [u8] {
+        match self {
+            NameFont::Brut => include_bytes!("../assets/fonts/OTBrut-Regular.ttf"),
+            NameFont::Monument => {
+                include_bytes!("../assets/fonts/PPMonumentCondensed-BoldItalic.ttf")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ContainerShape {
+    Off,
+    Cylinder,
+    Shaped, // follows the outside of the body
+}
+
+// Common neodymium disc magnets
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MagnetSize {
+    Off,
+    Mm6x2,
+    Mm8x3,
+    Mm10x3,
+    Mm12x3,
+}
+
+impl MagnetSize {
+    // (diameter, thickness) in mm
+    pub fn dimensions(&self) -> Option<(f64, f64)> {
+        match self {
+            MagnetSize::Off => None,
+            MagnetSize::Mm6x2 => Some((6.0, 2.0)),
+            MagnetSize::Mm8x3 => Some((8.0, 3.0)),
+            MagnetSize::Mm10x3 => Some((10.0, 3.0)),
+            MagnetSize::Mm12x3 => Some((12.0, 3.0)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FilamentDiameter {
+    Mm175,
+    Mm285,
+}
+
+impl FilamentDiameter {
+    pub fn millimeters(&self) -> f64 {
+        match self {
+            FilamentDiameter::Mm175 => 1.75,
+            FilamentDiameter::Mm285 => 2.85,
+        }
+    }
 }
 
 pub enum BirdGenInputTypes {
@@ -63,24 +562,130 @@ pub enum BirdGenInputTypes {
     BeakSize,
     BeakWidth,
     BeakRoundness,
+    BeakCurve,
+    BeakDroop,
     HeadSize,
     HeadToBelly,
     EyeSize,
+    EyeSpacing,
+    EyeAngle,
+    EyeBeadSize,
     HeadLateralOffset,
     HeadLevel,
     HeadYaw,
     HeadPitch,
+    NeckFillet,
+    CrestHeight,
+    CrestPlumes,
+    CrestSweep,
+    CrestThickness,
     BellyLength,
     BellySize,
     BellyFat,
     BellyToBottom,
     BottomSize,
+    NeckLength,
+    BellySag,
+    BackArch,
+    BlendRadius,
+    WingLength,
+    WingWidth,
+    WingDroop,
+    WingSpread,
     TailLength,
     TailWidth,
     TailYaw,
     TailPitch,
     TailRoundness,
+    TailFeathers,
+    TailSpread,
+    TailFork,
+    TailPoint,
+    TailCock,
+    BreastPatch,
+    WingBars,
+    CheekSpots,
+    FaceMask,
     BaseFlat,
+    LegLength,
+    LegThickness,
+    LegSpacing,
+    ToeCount,
+    ToeSplay,
+    PerchDiameter,
+    PerchHeight,
+    NameSize,
+    NameDepth,
+    LoopDiameter,
+    LoopPosition,
+    ScrewDiameter,
+    ScrewDepth,
+    ContainerWall,
+    ContainerOpening,
+    DrainageDiameter,
+    WhistleChamber,
+    WindwayWidth,
+    Subdivisions,
+    SmoothingPasses,
+    SmoothingAngle,
+    LowPolyFaces,
+    VoxelSize,
+    CutterWall,
+    SheetThickness,
+    SliceSpacing,
+    SheetWidth,
+    SheetHeight,
+    PaperFaces,
+    PaperScale,
+    MouldWall,
+    KeyClearance,
+    SprueDiameter,
+    VentCount,
+    ReliefDepth,
+    PlateThickness,
+    PlateBorder,
+    JumpRingDiameter,
+    PegDiameter,
+    PegLength,
+    PegClearance,
+    MaterialDensity,
+    LayerHeight,
+    PrintSpeed,
+    Infill,
+    ColourHue,
+    ColourSaturation,
+    ColourLightness,
+}
+
+// Non-slider inputs, shown as buttons that cycle through their values
+#[derive(Clone, Copy)]
+pub enum BirdGenOptionTypes {
+    BeakShape,
+    EyeStyle,
+    BodyStyle,
+    Geometry,
+    WingPose,
+    TailStyle,
+    Stand,
+    NamePlate,
+    NameStyle,
+    NameFont,
+    MagnetSize,
+    Container,
+    Whistle,
+    LowPoly,
+    Blocks,
+    OutlineView,
+    CookieCutter,
+    SliceAxis,
+    Sliceform,
+    PaperSize,
+    Relief,
+    ReliefPlate,
+    JumpRing,
+    SplitHead,
+    FilamentDiameter,
+    ColourPart,
 }
 
 pub fn get_input_type_string(input_type: &BirdGenInputTypes) -> &str {
@@ -89,24 +694,99 @@ pub fn get_input_type_string(input_type: &BirdGenInputTypes) -> &str {
         BirdGenInputTypes::BeakSize => "Beak Size",
         BirdGenInputTypes::BeakWidth => "Beak Width",
         BirdGenInputTypes::BeakRoundness => "Beak Roundness",
+        BirdGenInputTypes::BeakCurve => "Beak Curve",
+        BirdGenInputTypes::BeakDroop => "Tip Droop",
         BirdGenInputTypes::HeadSize => "Head Size",
         BirdGenInputTypes::HeadToBelly => "Head to Belly",
         BirdGenInputTypes::EyeSize => "Eye Size",
+        BirdGenInputTypes::EyeSpacing => "Eye Spacing",
+        BirdGenInputTypes::EyeAngle => "Eye Angle",
+        BirdGenInputTypes::EyeBeadSize => "Eye Bead Size",
         BirdGenInputTypes::HeadLateralOffset => "Head Lateral Offset",
         BirdGenInputTypes::HeadLevel => "Head Level",
         BirdGenInputTypes::HeadYaw => "Head Yaw",
         BirdGenInputTypes::HeadPitch => "Head Pitch",
+        BirdGenInputTypes::NeckFillet => "Neck Fillet",
+        BirdGenInputTypes::CrestHeight => "Crest Height",
+        BirdGenInputTypes::CrestPlumes => "Crest Plumes",
+        BirdGenInputTypes::CrestSweep => "Crest Sweep",
+        BirdGenInputTypes::CrestThickness => "Crest Thickness",
         BirdGenInputTypes::BellyLength => "Belly Length",
         BirdGenInputTypes::BellySize => "Belly Size",
         BirdGenInputTypes::BellyFat => "Belly Fat",
         BirdGenInputTypes::BellyToBottom => "Belly to Bottom",
         BirdGenInputTypes::BottomSize => "Bottom Size",
+        BirdGenInputTypes::NeckLength => "Neck Length",
+        BirdGenInputTypes::BellySag => "Belly Sag",
+        BirdGenInputTypes::BackArch => "Back Arch",
+        BirdGenInputTypes::BlendRadius => "Blend Radius",
+        BirdGenInputTypes::WingLength => "Wing Length",
+        BirdGenInputTypes::WingWidth => "Wing Width",
+        BirdGenInputTypes::WingDroop => "Wing Droop",
+        BirdGenInputTypes::WingSpread => "Wing Spread",
         BirdGenInputTypes::TailLength => "Tail Length",
         BirdGenInputTypes::TailWidth => "Tail Width",
         BirdGenInputTypes::TailYaw => "Tail Yaw",
         BirdGenInputTypes::TailPitch => "Tail Pitch",
         BirdGenInputTypes::TailRoundness => "Tail Roundness",
+        BirdGenInputTypes::TailFeathers => "Fan Feathers",
+        BirdGenInputTypes::TailSpread => "Tail Spread",
+        BirdGenInputTypes::TailFork => "Fork Depth",
+        BirdGenInputTypes::TailPoint => "Point Length",
+        BirdGenInputTypes::TailCock => "Wren Cock",
+        BirdGenInputTypes::BreastPatch => "Breast Patch",
+        BirdGenInputTypes::WingBars => "Wing Bars",
+        BirdGenInputTypes::CheekSpots => "Cheek Spots",
+        BirdGenInputTypes::FaceMask => "Face Mask",
         BirdGenInputTypes::BaseFlat => "Base Flat",
+        BirdGenInputTypes::LegLength => "Leg Length",
+        BirdGenInputTypes::LegThickness => "Leg Thickness",
+        BirdGenInputTypes::LegSpacing => "Leg Spacing",
+        BirdGenInputTypes::ToeCount => "Toe Count",
+        BirdGenInputTypes::ToeSplay => "Toe Splay",
+        BirdGenInputTypes::PerchDiameter => "Perch Diameter",
+        BirdGenInputTypes::PerchHeight => "Perch Height",
+        BirdGenInputTypes::NameSize => "Text Size",
+        BirdGenInputTypes::NameDepth => "Text Depth",
+        BirdGenInputTypes::LoopDiameter => "Loop Diameter",
+        BirdGenInputTypes::LoopPosition => "Loop Position",
+        BirdGenInputTypes::ScrewDiameter => "Screw Diameter",
+        BirdGenInputTypes::ScrewDepth => "Screw Depth",
+        BirdGenInputTypes::ContainerWall => "Wall Thickness",
+        BirdGenInputTypes::ContainerOpening => "Opening",
+        BirdGenInputTypes::DrainageDiameter => "Drainage Hole",
+        BirdGenInputTypes::WhistleChamber => "Chamber Size",
+        BirdGenInputTypes::WindwayWidth => "Windway Width",
+        BirdGenInputTypes::Subdivisions => "Subdivisions",
+        BirdGenInputTypes::SmoothingPasses => "Smoothing Passes",
+        BirdGenInputTypes::SmoothingAngle => "Smoothing Angle",
+        BirdGenInputTypes::LowPolyFaces => "Low Poly Faces",
+        BirdGenInputTypes::VoxelSize => "Block Size",
+        BirdGenInputTypes::CutterWall => "Cutter Wall",
+        BirdGenInputTypes::SheetThickness => "Sheet Thickness",
+        BirdGenInputTypes::SliceSpacing => "Slice Spacing",
+        BirdGenInputTypes::SheetWidth => "Sheet Width",
+        BirdGenInputTypes::SheetHeight => "Sheet Height",
+        BirdGenInputTypes::PaperFaces => "Paper Faces",
+        BirdGenInputTypes::PaperScale => "Paper Scale",
+        BirdGenInputTypes::MouldWall => "Mould Wall",
+        BirdGenInputTypes::KeyClearance => "Key Clearance",
+        BirdGenInputTypes::SprueDiameter => "Sprue Diameter",
+        BirdGenInputTypes::VentCount => "Vents",
+        BirdGenInputTypes::ReliefDepth => "Relief Depth",
+        BirdGenInputTypes::PlateThickness => "Plate Thickness",
+        BirdGenInputTypes::PlateBorder => "Plate Border",
+        BirdGenInputTypes::JumpRingDiameter => "Ring Hole",
+        BirdGenInputTypes::PegDiameter => "Peg Diameter",
+        BirdGenInputTypes::PegLength => "Peg Length",
+        BirdGenInputTypes::PegClearance => "Peg Clearance",
+        BirdGenInputTypes::MaterialDensity => "Material Density",
+        BirdGenInputTypes::LayerHeight => "Layer Height",
+        BirdGenInputTypes::PrintSpeed => "Print Speed",
+        BirdGenInputTypes::Infill => "Infill",
+        BirdGenInputTypes::ColourHue => "Hue",
+        BirdGenInputTypes::ColourSaturation => "Saturation",
+        BirdGenInputTypes::ColourLightness => "Lightness",
     }
 }
 
@@ -119,27 +799,396 @@ pub fn get_input_value_for_type(
         BirdGenInputTypes::BeakSize => input_values.beak_size,
         BirdGenInputTypes::BeakWidth => input_values.beak_width,
         BirdGenInputTypes::BeakRoundness => input_values.beak_roundness,
+        BirdGenInputTypes::BeakCurve => input_values.beak_curve,
+        BirdGenInputTypes::BeakDroop => input_values.beak_droop,
         BirdGenInputTypes::HeadSize => input_values.head_size,
         BirdGenInputTypes::HeadToBelly => input_values.head_to_belly,
         BirdGenInputTypes::EyeSize => input_values.eye_size,
+        BirdGenInputTypes::EyeSpacing => input_values.eye_spacing,
+        BirdGenInputTypes::EyeAngle => input_values.eye_angle,
+        BirdGenInputTypes::EyeBeadSize => input_values.eye_bead_size,
         BirdGenInputTypes::HeadLateralOffset => input_values.head_lateral_offset,
         BirdGenInputTypes::HeadLevel => input_values.head_level,
         BirdGenInputTypes::HeadYaw => input_values.head_yaw,
         BirdGenInputTypes::HeadPitch => input_values.head_pitch,
+        BirdGenInputTypes::NeckFillet => input_values.neck_fillet,
+        BirdGenInputTypes::CrestHeight => input_values.crest_height,
+        BirdGenInputTypes::CrestPlumes => input_values.crest_plumes,
+        BirdGenInputTypes::CrestSweep => input_values.crest_sweep,
+        BirdGenInputTypes::CrestThickness => input_values.crest_thickness,
         BirdGenInputTypes::BellyLength => input_values.belly_length,
         BirdGenInputTypes::BellySize => input_values.belly_size,
         BirdGenInputTypes::BellyFat => input_values.belly_fat,
         BirdGenInputTypes::BellyToBottom => input_values.belly_to_bottom,
         BirdGenInputTypes::BottomSize => input_values.bottom_size,
+        BirdGenInputTypes::NeckLength => input_values.neck_length,
+        BirdGenInputTypes::BellySag => input_values.belly_sag,
+        BirdGenInputTypes::BackArch => input_values.back_arch,
+        BirdGenInputTypes::BlendRadius => input_values.blend_radius,
+        BirdGenInputTypes::WingLength => input_values.wing_length,
+        BirdGenInputTypes::WingWidth => input_values.wing_width,
+        BirdGenInputTypes::WingDroop => input_values.wing_droop,
+        BirdGenInputTypes::WingSpread => input_values.wing_spread,
         BirdGenInputTypes::TailLength => input_values.tail_length,
         BirdGenInputTypes::TailWidth => input_values.tail_width,
         BirdGenInputTypes::TailYaw => input_values.tail_yaw,
         BirdGenInputTypes::TailPitch => input_values.tail_pitch,
         BirdGenInputTypes::TailRoundness => input_values.tail_roundness,
+        BirdGenInputTypes::TailFeathers => input_values.tail_feathers,
+        BirdGenInputTypes::TailSpread => input_values.tail_spread,
+        BirdGenInputTypes::TailFork => input_values.tail_fork,
+        BirdGenInputTypes::TailPoint => input_values.tail_point,
+        BirdGenInputTypes::TailCock => input_values.tail_cock,
+        BirdGenInputTypes::BreastPatch => input_values.breast_patch,
+        BirdGenInputTypes::WingBars => input_values.wing_bars,
+        BirdGenInputTypes::CheekSpots => input_values.cheek_spots,
+        BirdGenInputTypes::FaceMask => input_values.face_mask,
         BirdGenInputTypes::BaseFlat => input_values.base_flat,
+        BirdGenInputTypes::LegLength => input_values.leg_length,
+        BirdGenInputTypes::LegThickness => input_values.leg_thickness,
+        BirdGenInputTypes::LegSpacing => input_values.leg_spacing,
+        BirdGenInputTypes::ToeCount => input_values.toe_count,
+        BirdGenInputTypes::ToeSplay => input_values.toe_splay,
+        BirdGenInputTypes::PerchDiameter => input_values.perch_diameter,
+        BirdGenInputTypes::PerchHeight => input_values.perch_height,
+        BirdGenInputTypes::NameSize => input_values.name_size,
+        BirdGenInputTypes::NameDepth => input_values.name_depth,
+        BirdGenInputTypes::LoopDiameter => input_values.loop_diameter,
+        BirdGenInputTypes::LoopPosition => input_values.loop_position,
+        BirdGenInputTypes::ScrewDiameter => input_values.screw_diameter,
+        BirdGenInputTypes::ScrewDepth => input_values.screw_depth,
+        BirdGenInputTypes::ContainerWall => input_values.container_wall,
+        BirdGenInputTypes::ContainerOpening => input_values.container_opening,
+        BirdGenInputTypes::DrainageDiameter => input_values.drainage_diameter,
+        BirdGenInputTypes::WhistleChamber => input_values.whistle_chamber,
+        BirdGenInputTypes::WindwayWidth => input_values.windway_width,
+        BirdGenInputTypes::Subdivisions => input_values.subdivisions,
+        BirdGenInputTypes::SmoothingPasses => input_values.smoothing_passes,
+        BirdGenInputTypes::SmoothingAngle => input_values.smoothing_angle,
+        BirdGenInputTypes::LowPolyFaces => input_values.low_poly_faces,
+        BirdGenInputTypes::VoxelSize => input_values.voxel_size,
+        BirdGenInputTypes::CutterWall => input_values.cutter_wall,
+        BirdGenInputTypes::SheetThickness => input_values.sheet_thickness,
+        BirdGenInputTypes::SliceSpacing => input_values.slice_spacing,
+        BirdGenInputTypes::SheetWidth => input_values.sheet_width,
+        BirdGenInputTypes::SheetHeight => input_values.sheet_height,
+        BirdGenInputTypes::PaperFaces => input_values.paper_faces,
+        BirdGenInputTypes::PaperScale => input_values.paper_scale,
+        BirdGenInputTypes::MouldWall => input_values.mould_wall,
+        BirdGenInputTypes::KeyClearance => input_values.key_clearance,
+        BirdGenInputTypes::SprueDiameter => input_values.sprue_diameter,
+        BirdGenInputTypes::VentCount => input_values.vent_count,
+        BirdGenInputTypes::ReliefDepth => input_values.relief_depth,
+        BirdGenInputTypes::PlateThickness => input_values.plate_thickness,
+        BirdGenInputTypes::PlateBorder => input_values.plate_border,
+        BirdGenInputTypes::JumpRingDiameter => input_values.jump_ring_diameter,
+        BirdGenInputTypes::PegDiameter => input_values.peg_diameter,
+        BirdGenInputTypes::PegLength => input_values.peg_length,
+        BirdGenInputTypes::PegClearance => input_values.peg_clearance,
+        BirdGenInputTypes::MaterialDensity => input_values.material_density,
+        BirdGenInputTypes::LayerHeight => input_values.layer_height,
+        BirdGenInputTypes::PrintSpeed => input_values.print_speed,
+        BirdGenInputTypes::Infill => input_values.infill,
+        BirdGenInputTypes::ColourHue => part_colour(input_values.colour_part, input_values).hue,
+        BirdGenInputTypes::ColourSaturation => {
+            part_colour(input_values.colour_part, input_values).saturation
+        }
+        BirdGenInputTypes::ColourLightness => {
+            part_colour(input_values.colour_part, input_values).lightness
+        }
+    }
+}
+
+pub fn get_option_type_string(option_type: &BirdGenOptionTypes) -> &str {
+    match option_type {
+        BirdGenOptionTypes::BeakShape => "Beak Shape",
+        BirdGenOptionTypes::EyeStyle => "Eye Style",
+        BirdGenOptionTypes::BodyStyle => "Body Style",
+        BirdGenOptionTypes::Geometry => "Geometry",
+        BirdGenOptionTypes::WingPose => "Wing Pose",
+        BirdGenOptionTypes::TailStyle => "Tail Style",
+        BirdGenOptionTypes::Stand => "Stand",
+        BirdGenOptionTypes::NamePlate => "Name Plate",
+        BirdGenOptionTypes::NameStyle => "Text",
+        BirdGenOptionTypes::NameFont => "Font",
+        BirdGenOptionTypes::MagnetSize => "Magnet",
+        BirdGenOptionTypes::Container => "Container",
+        BirdGenOptionTypes::Whistle => "Whistle",
+        BirdGenOptionTypes::LowPoly => "Low Poly",
+        BirdGenOptionTypes::Blocks => "Blocks",
+        BirdGenOptionTypes::OutlineView => "View",
+        BirdGenOptionTypes::CookieCutter => "Cookie Cutter",
+        BirdGenOptionTypes::SliceAxis => "Slice Along",
+        BirdGenOptionTypes::Sliceform => "Sliceform",
+        BirdGenOptionTypes::PaperSize => "Paper",
+        BirdGenOptionTypes::Relief => "Relief",
+        BirdGenOptionTypes::ReliefPlate => "Backing Plate",
+        BirdGenOptionTypes::JumpRing => "Jump Ring",
+        BirdGenOptionTypes::SplitHead => "Split Head",
+        BirdGenOptionTypes::FilamentDiameter => "Filament",
+        BirdGenOptionTypes::ColourPart => "Part",
+    }
+}
+
+pub fn get_option_value_string(
+    option_type: &BirdGenOptionTypes,
+    input_values: &BirdGenInputs,
+) -> &'static str {
+    match option_type {
+        BirdGenOptionTypes::BeakShape => match input_values.beak_shape {
+            BeakShape::Straight => "Straight",
+            BeakShape::Hooked => "Hooked",
+            BeakShape::Curved => "Curved",
+            BeakShape::Duck => "Duck Bill",
+        },
+        BirdGenOptionTypes::EyeStyle => match input_values.eye_style {
+            EyeStyle::Bump => "Bumps",
+            EyeStyle::Socket => "Sockets",
+            EyeStyle::Domed => "Domes + Pupils",
+        },
+        BirdGenOptionTypes::BodyStyle => match input_values.body_style {
+            BodyStyle::Hull => "Sphere Hull",
+            BodyStyle::Lofted => "Lofted",
+        },
+        BirdGenOptionTypes::Geometry => match input_values.geometry {
+            GeometryBackend::Csg => "CSG Hulls",
+            GeometryBackend::Sdf => "SDF Blend",
+        },
+        BirdGenOptionTypes::WingPose => match input_values.wing_pose {
+            WingPose::Folded => "Folded",
+            WingPose::Spread => "Spread",
+        },
+        BirdGenOptionTypes::TailStyle => match input_values.tail_style {
+            TailStyle::Classic => "Classic",
+            TailStyle::Forked => "Forked",
+            TailStyle::Fan => "Fan",
+            TailStyle::Pointed => "Pointed",
+            TailStyle::Wren => "Wren",
+        },
+        BirdGenOptionTypes::Stand => match input_values.stand {
+            BirdStand::Belly => "Flat Base",
+            BirdStand::Legs => "Legs",
+            BirdStand::Perch => "Perch",
+        },
+        BirdGenOptionTypes::NamePlate => match input_values.name_plate {
+            NamePlate::Off => "Off",
+            NamePlate::Base => "Under Base",
+            NamePlate::Pedestal => "Pedestal",
+        },
+        BirdGenOptionTypes::NameStyle => match input_values.name_style {
+            NameStyle::Engraved => "Engraved",
+            NameStyle::Embossed => "Embossed",
+        },
+        BirdGenOptionTypes::NameFont => match input_values.name_font {
+            NameFont::Brut => "OT Brut",
+            NameFont::Monument => "Monument",
+        },
+        BirdGenOptionTypes::MagnetSize => match input_values.magnet_size {
+            MagnetSize::Off => "Off",
+            MagnetSize::Mm6x2 => "6 x 2mm",
+            MagnetSize::Mm8x3 => "8 x 3mm",
+            MagnetSize::Mm10x3 => "10 x 3mm",
+            MagnetSize::Mm12x3 => "12 x 3mm",
+        },
+        BirdGenOptionTypes::Container => match input_values.container {
+            ContainerShape::Off => "Off",
+            ContainerShape::Cylinder => "Cylinder",
+            ContainerShape::Shaped => "Shaped",
+        },
+        BirdGenOptionTypes::Whistle => on_off_string(input_values.whistle),
+        BirdGenOptionTypes::LowPoly => on_off_string(input_values.low_poly),
+        BirdGenOptionTypes::Blocks => match input_values.blocks {
+            BlockStyle::Off => "Off",
+            BlockStyle::Voxels => "Voxels",
+            BlockStyle::Bricks => "Bricks",
+        },
+        BirdGenOptionTypes::OutlineView => match input_values.outline_view {
+            OutlineView::Side => "Side",
+            OutlineView::Top => "Top",
+            OutlineView::Front => "Front",
+        },
+        BirdGenOptionTypes::CookieCutter => on_off_string(input_values.cookie_cutter),
+        BirdGenOptionTypes::SliceAxis => match input_values.slice_axis {
+            SliceAxis::Length => "Length",
+            SliceAxis::Width => "Width",
+            SliceAxis::Height => "Height",
+        },
+        BirdGenOptionTypes::Sliceform => on_off_string(input_values.sliceform),
+        BirdGenOptionTypes::PaperSize => match input_values.paper_size {
+            PaperSize::A4 => "A4",
+            PaperSize::Letter => "Letter",
+        },
+        BirdGenOptionTypes::Relief => on_off_string(input_values.relief),
+        BirdGenOptionTypes::ReliefPlate => on_off_string(input_values.relief_plate),
+        BirdGenOptionTypes::JumpRing => on_off_string(input_values.jump_ring),
+        BirdGenOptionTypes::SplitHead => on_off_string(input_values.split_head),
+        BirdGenOptionTypes::FilamentDiameter => match input_values.filament_diameter {
+            FilamentDiameter::Mm175 => "1.75mm",
+            FilamentDiameter::Mm285 => "2.85mm",
+        },
+        BirdGenOptionTypes::ColourPart => input_values.colour_part.name(),
+    }
+}
+
+// Step the option to its next value (wrapping around)
+pub fn cycle_option_value(option_type: &BirdGenOptionTypes, input_values: &mut BirdGenInputs) {
+    match option_type {
+        BirdGenOptionTypes::BeakShape => {
+            input_values.beak_shape = match input_values.beak_shape {
+                BeakShape::Straight => BeakShape::Hooked,
+                BeakShape::Hooked => BeakShape::Curved,
+                BeakShape::Curved => BeakShape::Duck,
+                BeakShape::Duck => BeakShape::Straight,
+            }
+        }
+        BirdGenOptionTypes::EyeStyle => {
+            input_values.eye_style = match input_values.eye_style {
+                EyeStyle::Bump => EyeStyle::Socket,
+                EyeStyle::Socket => EyeStyle::Domed,
+                EyeStyle::Domed => EyeStyle::Bump,
+            }
+        }
+        // the SDF backend only does the hull body, so lofting switches back to csgrs and the
+        // other way round
+        BirdGenOptionTypes::BodyStyle => {
+            input_values.body_style = match input_values.body_style {
+                BodyStyle::Hull => BodyStyle::Lofted,
+                BodyStyle::Lofted => BodyStyle::Hull,
+            };
+            if input_values.body_style == BodyStyle::Lofted {
+                input_values.geometry = GeometryBackend::Csg;
+            }
+        }
+        BirdGenOptionTypes::Geometry => {
+            input_values.geometry = match input_values.geometry {
+                GeometryBackend::Csg => GeometryBackend::Sdf,
+                GeometryBackend::Sdf => GeometryBackend::Csg,
+            };
+            if input_values.geometry == GeometryBackend::Sdf {
+                input_values.body_style = BodyStyle::Hull;
+            }
+        }
+        BirdGenOptionTypes::WingPose => {
+            input_values.wing_pose = match input_values.wing_pose {
+                WingPose::Folded => WingPose::Spread,
+                WingPose::Spread => WingPose::Folded,
+            }
+        }
+        BirdGenOptionTypes::TailStyle => {
+            input_values.tail_style = match input_values.tail_style {
+                TailStyle::Classic => TailStyle::Forked,
+                TailStyle::Forked => TailStyle::Fan,
+                TailStyle::Fan => TailStyle::Pointed,
+                TailStyle::Pointed => TailStyle::Wren,
+                TailStyle::Wren => TailStyle::Classic,
+            }
+        }
+        BirdGenOptionTypes::Stand => {
+            input_values.stand = match input_values.stand {
+                BirdStand::Belly => BirdStand::Legs,
+                BirdStand::Legs => BirdStand::Perch,
+                BirdStand::Perch => BirdStand::Belly,
+            }
+        }
+        BirdGenOptionTypes::NamePlate => {
+            input_values.name_plate = match input_values.name_plate {
+                NamePlate::Off => NamePlate::Base,
+                NamePlate::Base => NamePlate::Pedestal,
+                NamePlate::Pedestal => NamePlate::Off,
+            }
+        }
+        BirdGenOptionTypes::NameStyle => {
+            input_values.name_style = match input_values.name_style {
+                NameStyle::Engraved => NameStyle::Embossed,
+                NameStyle::Embossed => NameStyle::Engraved,
+            }
+        }
+        BirdGenOptionTypes::NameFont => {
+            input_values.name_font = match input_values.name_font {
+                NameFont::Brut => NameFont::Monument,
+                NameFont::Monument => NameFont::Brut,
+            }
+        }
+        BirdGenOptionTypes::MagnetSize => {
+            input_values.magnet_size = match input_values.magnet_size {
+                MagnetSize::Off => MagnetSize::Mm6x2,
+                MagnetSize::Mm6x2 => MagnetSize::Mm8x3,
+                MagnetSize::Mm8x3 => MagnetSize::Mm10x3,
+                MagnetSize::Mm10x3 => MagnetSize::Mm12x3,
+                MagnetSize::Mm12x3 => MagnetSize::Off,
+            }
+        }
+        BirdGenOptionTypes::Container => {
+            input_values.container = match input_values.container {
+                ContainerShape::Off => ContainerShape::Cylinder,
+                ContainerShape::Cylinder => ContainerShape::Shaped,
+                ContainerShape::Shaped => ContainerShape::Off,
+            }
+        }
+        BirdGenOptionTypes::Whistle => input_values.whistle = !input_values.whistle,
+        BirdGenOptionTypes::LowPoly => input_values.low_poly = !input_values.low_poly,
+        BirdGenOptionTypes::Blocks => {
+            input_values.blocks = match input_values.blocks {
+                BlockStyle::Off => BlockStyle::Voxels,
+                BlockStyle::Voxels => BlockStyle::Bricks,
+                BlockStyle::Bricks => BlockStyle::Off,
+            }
+        }
+        BirdGenOptionTypes::OutlineView => {
+            input_values.outline_view = match input_values.outline_view {
+                OutlineView::Side => OutlineView::Top,
+                OutlineView::Top => OutlineView::Front,
+                OutlineView::Front => OutlineView::Side,
+            }
+        }
+        BirdGenOptionTypes::CookieCutter => {
+            input_values.cookie_cutter = !input_values.cookie_cutter
+        }
+        BirdGenOptionTypes::SliceAxis => {
+            input_values.slice_axis = match input_values.slice_axis {
+                SliceAxis::Length => SliceAxis::Width,
+                SliceAxis::Width => SliceAxis::Height,
+                SliceAxis::Height => SliceAxis::Length,
+            }
+        }
+        BirdGenOptionTypes::Sliceform => input_values.sliceform = !input_values.sliceform,
+        BirdGenOptionTypes::PaperSize => {
+            input_values.paper_size = match input_values.paper_size {
+                PaperSize::A4 => PaperSize::Letter,
+                PaperSize::Letter => PaperSize::A4,
+            }
+        }
+        BirdGenOptionTypes::Relief => input_values.relief = !input_values.relief,
+        BirdGenOptionTypes::ReliefPlate => input_values.relief_plate = !input_values.relief_plate,
+        BirdGenOptionTypes::JumpRing => input_values.jump_ring = !input_values.jump_ring,
+        BirdGenOptionTypes::SplitHead => input_values.split_head = !input_values.split_head,
+        BirdGenOptionTypes::FilamentDiameter => {
+            input_values.filament_diameter = match input_values.filament_diameter {
+                FilamentDiameter::Mm175 => FilamentDiameter::Mm285,
+                FilamentDiameter::Mm285 => FilamentDiameter::Mm175,
+            }
+        }
+        BirdGenOptionTypes::ColourPart => {
+            input_values.colour_part = match input_values.colour_part {
+                BirdPart::Body => BirdPart::Head,
+                BirdPart::Head => BirdPart::Beak,
+                BirdPart::Beak => BirdPart::Eyes,
+                BirdPart::Eyes => BirdPart::Pupils,
+                BirdPart::Pupils => BirdPart::Wings,
+                BirdPart::Wings => BirdPart::BreastPatch,
+                BirdPart::BreastPatch => BirdPart::WingBars,
+                BirdPart::WingBars => BirdPart::CheekSpots,
+                BirdPart::CheekSpots => BirdPart::FaceMask,
+                BirdPart::FaceMask => BirdPart::Body,
+            }
+        }
     }
 }
 
+fn on_off_string(value: bool) -> &'static str {
+    if value { "On" } else { "Off" }
+}
+
 impl Default for BirdGenInputs {
     fn default() -> Self {
         BirdGenInputs {
@@ -147,24 +1196,134 @@ impl Default for BirdGenInputs {
             beak_size: 100.0,
             beak_width: 5.0,
             beak_roundness: 10.0,
+            beak_shape: BeakShape::Straight,
+            beak_curve: 20.0,
+            beak_droop: 40.0,
             head_size: 22.0,
             head_to_belly: 32.0,
             eye_size: 5.0,
+            eye_style: EyeStyle::Bump,
+            eye_spacing: 50.0,
+            eye_angle: 40.0,
+            eye_bead_size: 6.0,
             head_lateral_offset: 4.0,
             head_level: 32.0,
             head_yaw: 10.0,
             head_pitch: 9.0,
+            neck_fillet: 0.0,
+            crest_height: 0.0,
+            crest_plumes: 5.0,
+            crest_sweep: 35.0,
+            crest_thickness: 3.0,
             belly_length: 60.0,
             belly_size: 40.0,
             belly_fat: 90.0,
             belly_to_bottom: 25.0,
             bottom_size: 25.0,
+            body_style: BodyStyle::Hull,
+            neck_length: 10.0,
+            belly_sag: 10.0,
+            back_arch: 0.0,
+            geometry: GeometryBackend::Csg,
+            blend_radius: 8.0,
+            wing_length: 45.0,
+            wing_width: 22.0,
+            wing_droop: 15.0,
+            wing_spread: 10.0,
+            wing_pose: WingPose::Folded,
             tail_length: 50.0,
             tail_width: 22.0,
             tail_yaw: -5.0,
             tail_pitch: 40.0,
             tail_roundness: 80.0,
+            tail_style: TailStyle::Classic,
+            tail_feathers: 7.0,
+            tail_spread: 60.0,
+            tail_fork: 50.0,
+            tail_point: 80.0,
+            tail_cock: 80.0,
+            breast_patch: 0.0,
+            wing_bars: 0.0,
+            cheek_spots: 0.0,
+            face_mask: 0.0,
             base_flat: 50.0,
+            stand: BirdStand::Belly,
+            leg_length: 20.0,
+            leg_thickness: 4.0,
+            leg_spacing: 14.0,
+            toe_count: 3.0,
+            toe_splay: 60.0,
+            perch_diameter: 8.0,
+            perch_height: 20.0,
+            name_text: String::new(),
+            name_plate: NamePlate::Off,
+            name_style: NameStyle::Engraved,
+            name_font: NameFont::Brut,
+            name_size: 8.0,
+            name_depth: 1.0,
+            loop_diameter: 0.0,
+            loop_position: 30.0,
+            magnet_size: MagnetSize::Off,
+            screw_diameter: 0.0,
+            screw_depth: 6.0,
+            container: ContainerShape::Off,
+            container_wall: 2.4,
+            container_opening: 25.0,
+            drainage_diameter: 0.0,
+            whistle: false,
+            whistle_chamber: 35.0,
+            windway_width: 6.0,
+            subdivisions: 0.0,
+            smoothing_passes: 0.0,
+            smoothing_angle: 0.0,
+            low_poly: false,
+            low_poly_faces: 600.0,
+            blocks: BlockStyle::Off,
+            voxel_size: 4.0,
+            outline_view: OutlineView::Side,
+            cookie_cutter: false,
+            cutter_wall: 1.2,
+            slice_axis: SliceAxis::Height,
+            sheet_thickness: 3.0,
+            sliceform: false,
+            slice_spacing: 12.0,
+            sheet_width: 300.0,
+            sheet_height: 300.0,
+            paper_faces: 200.0,
+            paper_scale: 100.0,
+            paper_size: PaperSize::A4,
+            mould_wall: 10.0,
+            key_clearance: 0.2,
+            sprue_diameter: 6.0,
+            vent_count: 2.0,
+            relief: false,
+            relief_depth: 4.0,
+            relief_plate: true,
+            plate_thickness: 2.0,
+            plate_border: 2.0,
+            jump_ring: false,
+            jump_ring_diameter: 3.0,
+            split_head: false,
+            peg_diameter: 6.0,
+            peg_length: 8.0,
+            peg_clearance: 0.2,
+            material_density: 1.24,
+            filament_diameter: FilamentDiameter::Mm175,
+            layer_height: 0.2,
+            print_speed: 60.0,
+            infill: 15.0,
+            colour_part: BirdPart::Body,
+            // the original orange bird, with a yellow beak and darker wings
+            body_colour: PartColour::hsl(8.0, 66.0, 50.0),
+            head_colour: PartColour::hsl(8.0, 66.0, 50.0),
+            beak_colour: PartColour::hsl(40.0, 90.0, 55.0),
+            eye_colour: PartColour::hsl(45.0, 30.0, 92.0),
+            pupil_colour: PartColour::hsl(0.0, 0.0, 5.0),
+            wing_colour: PartColour::hsl(8.0, 60.0, 38.0),
+            breast_patch_colour: PartColour::hsl(12.0, 85.0, 45.0),
+            wing_bar_colour: PartColour::hsl(0.0, 0.0, 95.0),
+            cheek_spot_colour: PartColour::hsl(350.0, 70.0, 65.0),
+            face_mask_colour: PartColour::hsl(0.0, 0.0, 10.0),
         }
     }
 }
@@ -178,13 +1337,321 @@ const SPHERE_STACKS: usize = RESOLUTION_PSUEDO_UNIT * 2;
 
 const NONZERO_THICKNESS: f64 = 0.1; // used in place of 0 when we want parts of the bird to approach an edge
 
-// Currently making separate head and body meshes,
+const LOFT_RING_SEGMENTS: usize = RESOLUTION_PSUEDO_UNIT * 2;
+const LOFT_SPAN_SAMPLES: usize = 8; // rings between each pair of spine key sections
+const LOFT_BEND_LIMIT: f64 = 0.9; // how far a ring reaches towards the middle of a bend, relative to the bend radius
+const NECK_RATIO: f64 = 0.7; // lofted neck thickness relative to the head
+
+// Marching tetrahedra grid cells along the longest side. About 2mm cells on the default bird, well
+// under the blend radius, and the triangle count stays low enough for the csgrs booleans that
+// still happen afterwards (wings, base, markings..), which slow right down on dense meshes.
+const SDF_GRID_CELLS: usize = 64;
+const SDF_TAIL_FLATTEN: f64 = 0.2; // the SDF tail is a round cone squashed this much top to bottom
+const SDF_HEAD_INSET: f64 = 0.05; // the neck fillet's blended head sits just inside the real one, so cutting it back out leaves no skin
+
+const WING_THICKNESS_RATIO: f64 = 0.3;
+
+const BEAK_TILT: f64 = 15.0; // degrees the beak points up from the head's x axis
+const BEAK_LOFT_SECTIONS: usize = 8;
+const BEAK_HOOK_ANGLE: f64 = 150.0; // degrees the tip curls down with the droop at 100
+
+const EYE_DOME_HEIGHT_RATIO: f64 = 0.6;
+const PUPIL_RATIO: f64 = 0.45; // pupil size relative to the eye
+
+const CREST_FAN_ANGLE: f64 = 50.0; // degrees between the front and back plume
+
+const TAIL_ROOT_RATIO: f64 = 0.35; // size of the disc the body tapers into when the tail is made of feathers
+const TAIL_FEATHER_THICKNESS: f64 = 2.4; // at the root, they thin out to half this at the tip
+
+const WING_BAR_WIDTH_RATIO: f64 = 0.08; // relative to the wing length
+const CHEEK_SPOT_DROP: f64 = 35.0; // degrees further down the side of the head than the eyes
+
+const PERCH_BASE_THICKNESS: f64 = 4.0;
+
+const PEDESTAL_SINK: f64 = 1.0; // how far the pedestal top goes up into the bird, so the union holds
+
+const MAGNET_CLEARANCE: f64 = 0.15; // extra room around the magnet, so it presses in
+const SCREW_CLEARANCE: f64 = 0.2;
+const SCREW_HEAD_RATIO: f64 = 2.0; // countersunk head diameter relative to the screw
+const SCREW_HEAD_ROOM: f64 = 3.0; // blind pocket above the countersink, for the head and the screwdriver tip
+const SCREW_MAGNET_GAP: f64 = 2.0; // solid left between the magnet pocket and the screw hole
+
+const WHISTLE_WALL: f64 = 2.0; // thinnest the walls around the chamber and windway get
+const WINDWAY_HEIGHT: f64 = 1.2;
+const WINDWAY_FLOOR: f64 = 1.0; // solid between the windway and the chamber below it
+const WINDOW_LENGTH_RATIO: f64 = 3.5; // gap from the windway exit to the labium edge, relative to the windway height
+const LABIUM_LENGTH: f64 = 3.0; // how far the sharp part of the labium runs before it thickens out
+const LABIUM_TOP_ANGLE: f64 = 35.0; // degrees, the labium edge ends up 45 degrees sharp
+const LABIUM_BOTTOM_ANGLE: f64 = 10.0;
+const SPEED_OF_SOUND: f64 = 343_000.0; // mm/s
+
+const SMOOTHING_FLOOR_TOLERANCE: f64 = 1e-3; // how close to the bottom of the bird counts as on the build plate
+
+const HEAD_SCALE: f64 = 1.1; // the whole placed head gets scaled up a bit (around the origin!)
+
+// Every part of the bird is its own mesh,
 // Can't get a nice result when doing a union between the head and body
 // (something in the csgrs Mesh union logic I think might be too aggressive at deleting triangles? -- armchair dev view lol)
-// For now tho we'll just spawn two different meshes in Bevy, even though that would make printing it in 3d a bit harder.
-// We'll see!
+// So we spawn a mesh per part in Bevy (which also gives each part its own colour),
+// and the exports keep them as separate bodies for multi colour printing.
+// (the split head option leans into this, see `split_head_joint`)
+
+// The csgrs meshes are kept around (instead of going straight to bevy meshes) so we can measure and export them
+
+pub fn bird_csg_to_bevy_mesh(bird_part: &CSGMesh, input: &BirdGenInputs) -> Mesh {
+    info!("Make bevy mesh");
+
+    // add the x axis rotation to account for y up world we're rocking with in bevy
+    let indexed = IndexedMesh::from_csg(&bird_part.rotate(-90.0, 180.0, 0.0));
+    // every corner gets its own vertex, so creases can have different normals either side
+    let positions: Vec<[f32; 3]> = indexed
+        .triangles
+        .iter()
+        .flat_map(|triangle| triangle.map(|vertex| indexed.vertices[vertex].as_vec3().to_array()))
+        .collect();
+    let normals: Vec<[f32; 3]> = indexed
+        .corner_normals(shading_angle(input))
+        .iter()
+        .flat_map(|corners| corners.map(|normal| normal.as_vec3().to_array()))
+        .collect();
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+}
+
+// Smoothing angle for the normals (low poly and blocks are always flat shaded)
+pub fn shading_angle(input: &BirdGenInputs) -> f64 {
+    if input.low_poly || input.blocks != BlockStyle::Off {
+        0.0
+    } else {
+        input.smoothing_angle as f64
+    }
+}
+
+// All the parts of the bird, in the OpenSCAD style z up coordinates
+pub struct BirdParts {
+    pub body: CSGMesh,
+    pub head: CSGMesh,
+    pub beak: CSGMesh,
+    pub eyes: Option<CSGMesh>,   // socket eyes are just holes in the head
+    pub pupils: Option<CSGMesh>, // only for domed eyes
+    pub wings: Option<CSGMesh>,
+    // markings, each cut out of the part it's painted on
+    pub breast_patch: Option<CSGMesh>,
+    pub wing_bars: Option<CSGMesh>,
+    pub cheek_spots: Option<CSGMesh>,
+    pub face_mask: Option<CSGMesh>,
+    // the block version of the bird, when it's built from blocks
+    pub voxels: Option<VoxelBird>,
+}
+
+impl BirdParts {
+    // The parts this bird actually has, tagged with which part they are
+    pub fn iter(&self) -> impl Iterator<Item = (BirdPart, &CSGMesh)> {
+        [
+            (BirdPart::Body, Some(&self.body)),
+            (BirdPart::Head, Some(&self.head)),
+            (BirdPart::Beak, Some(&self.beak)),
+            (BirdPart::Eyes, self.eyes.as_ref()),
+            (BirdPart::Pupils, self.pupils.as_ref()),
+            (BirdPart::Wings, self.wings.as_ref()),
+            (BirdPart::BreastPatch, self.breast_patch.as_ref()),
+            (BirdPart::WingBars, self.wing_bars.as_ref()),
+            (BirdPart::CheekSpots, self.cheek_spots.as_ref()),
+            (BirdPart::FaceMask, self.face_mask.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(part, mesh)| mesh.map(|mesh| (part, mesh)))
+        // parts can end up empty (the head once it's joined onto the body, or too thin for a block)
+        .filter(|(_, mesh)| !mesh.polygons.is_empty())
+    }
+
+    pub fn meshes(&self) -> Vec<&CSGMesh> {
+        self.iter().map(|(_, mesh)| mesh).collect()
+    }
+
+    // Every part unioned into one solid, for measuring and for anything that has to print in one piece
+    pub fn solid(&self) -> CSGMesh {
+        self.meshes()
+            .into_iter()
+            .fold(CSGMesh::new(), |solid, part| solid.union(part))
+    }
+
+    pub fn meshes_mut(&mut self) -> impl Iterator<Item = &mut CSGMesh> {
+        [
+            Some(&mut self.body),
+            Some(&mut self.head),
+            Some(&mut self.beak),
+            self.eyes.as_mut(),
+            self.pupils.as_mut(),
+            self.wings.as_mut(),
+            self.breast_patch.as_mut(),
+            self.wing_bars.as_mut(),
+            self.cheek_spots.as_mut(),
+            self.face_mask.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        // same parts as `iter`
+        .filter(|mesh| !mesh.polygons.is_empty())
+    }
 
-pub fn generate_bird_head_mesh(input: &BirdGenInputs) -> Mesh {
+    // Subdivide and smooth every part. Whatever sits flat on the build plate stays put,
+    // so the bird keeps its flat base. The parts get welded together and smoothed as one mesh, so
+    // the seams where they meet (markings, eyes..) stay where they are and can't crack open.
+    fn smooth(&mut self, input: &BirdGenInputs) {
+        let floor = MeshStats::from_meshes(&self.meshes()).bounding_box_min.z;
+        let on_floor = |vertex: DVec3| vertex.z < floor + SMOOTHING_FLOOR_TOLERANCE;
+        let (mut indexed, mut triangle_parts) = IndexedMesh::from_csg_parts(&self.meshes());
+        for _ in 0..input.subdivisions.round() as usize {
+            indexed = indexed.loop_subdivide(on_floor);
+            // every triangle turns into four, in order
+            triangle_parts = triangle_parts.iter().flat_map(|part| [*part; 4]).collect();
+        }
+        indexed.taubin_smooth(input.smoothing_passes.round() as usize, on_floor);
+        for (part, mesh) in self.meshes_mut().enumerate() {
+            *mesh = indexed
+                .select(|triangle| triangle_parts[triangle] == part)
+                .to_csg();
+        }
+    }
+
+    // Quadric decimation of the whole bird at once, welded together like the smoothing, so the
+    // parts keep meeting where they did
+    fn decimate(&mut self, input: &BirdGenInputs) {
+        let (indexed, triangle_parts) = IndexedMesh::from_csg_parts(&self.meshes());
+        let (decimated, kept) = indexed.decimate_tracked(input.low_poly_faces as usize);
+        for (part, mesh) in self.meshes_mut().enumerate() {
+            *mesh = decimated
+                .select(|triangle| triangle_parts[kept[triangle]] == part)
+                .to_csg();
+        }
+    }
+
+    // Peg on the head and the seat and socket for it in the neck, for printing the head on its own.
+    // This goes on after smoothing and decimating, which would round the peg off and eat into the
+    // clearance.
+    fn cut_split_head_joint(&mut self, input: &BirdGenInputs) {
+        let (peg, socket) = split_head_joint(input);
+        self.head = self.head.union(&peg);
+        self.head.renormalize();
+        // take the head (peg included) out of the neck so the two parts mate along the skull,
+        // then open the socket up by the clearance so the peg slides in
+        let head_pieces = [&self.head, &self.beak]
+            .into_iter()
+            .chain(self.cheek_spots.as_ref())
+            .chain(self.face_mask.as_ref());
+        for head_piece in head_pieces {
+            self.body = self.body.difference(head_piece);
+        }
+        self.body = self.body.difference(&socket);
+        self.body.renormalize();
+    }
+
+    // Swap every part for its blocky version (a part too thin to fill a single cell goes away)
+    fn voxelize(&mut self, input: &BirdGenInputs) {
+        let voxels = voxelize(self, input);
+        self.body = voxels
+            .part_mesh(BirdPart::Body)
+            .unwrap_or_else(CSGMesh::new);
+        self.head = voxels
+            .part_mesh(BirdPart::Head)
+            .unwrap_or_else(CSGMesh::new);
+        self.beak = voxels
+            .part_mesh(BirdPart::Beak)
+            .unwrap_or_else(CSGMesh::new);
+        self.eyes = voxels.part_mesh(BirdPart::Eyes);
+        self.pupils = voxels.part_mesh(BirdPart::Pupils);
+        self.wings = voxels.part_mesh(BirdPart::Wings);
+        self.breast_patch = voxels.part_mesh(BirdPart::BreastPatch);
+        self.wing_bars = voxels.part_mesh(BirdPart::WingBars);
+        self.cheek_spots = voxels.part_mesh(BirdPart::CheekSpots);
+        self.face_mask = voxels.part_mesh(BirdPart::FaceMask);
+        info!("{} blocks", voxels.cells.len());
+        self.voxels = Some(voxels);
+    }
+}
+
+pub fn generate_bird_parts(input: &BirdGenInputs) -> BirdParts {
+    let mut head = generate_head_parts(input);
+    let body = generate_body_parts(input, &mut head);
+    let mut parts = BirdParts {
+        body: body.body,
+        head: head.head,
+        beak: head.beak,
+        eyes: head.eyes,
+        pupils: head.pupils,
+        wings: body.wings,
+        breast_patch: body.breast_patch,
+        wing_bars: body.wing_bars,
+        cheek_spots: head.cheek_spots,
+        face_mask: head.face_mask,
+        voxels: None,
+    };
+    if input.subdivisions >= 1.0 || input.smoothing_passes >= 1.0 {
+        info!("Smoothing parts");
+        parts.smooth(input);
+    }
+    if input.low_poly {
+        info!("Decimating to low poly");
+        parts.decimate(input);
+    }
+    if input.split_head {
+        info!("Adding alignment peg and carving its socket");
+        parts.cut_split_head_joint(input);
+    }
+    if input.relief {
+        info!("Flattening into a relief");
+        make_relief(&mut parts, input);
+    }
+    if input.blocks != BlockStyle::Off {
+        info!("Building from blocks");
+        parts.voxelize(input);
+    }
+    parts
+}
+
+// Split whatever is inside `volume` off of the part, so it can be a different colour.
+// Gives back the split off region (if the volume touched the part at all).
+fn paint_region(part: &mut CSGMesh, volume: &CSGMesh) -> Option<CSGMesh> {
+    let mut region = part.intersection(volume);
+    if region.polygons.is_empty() {
+        return None;
+    }
+    region.renormalize();
+    *part = part.difference(volume);
+    part.renormalize();
+    Some(region)
+}
+
+// Everything that moves with the head, placed on the body
+struct HeadParts {
+    head: CSGMesh,
+    beak: CSGMesh,
+    // skull and beak as the convex hulls they were built from, for the neck fillet
+    beak_hulls: Vec<CSGMesh>,
+    // skull and crest as a distance field, and the eye sockets and markings that come out of it,
+    // for blending the head into the SDF body
+    sdf: Sdf,
+    sdf_cuts: Vec<Sdf>,
+    eyes: Option<CSGMesh>,
+    pupils: Option<CSGMesh>,
+    cheek_spots: Option<CSGMesh>,
+    face_mask: Option<CSGMesh>,
+}
+
+// Body and everything attached to it
+struct BodyParts {
+    body: CSGMesh,
+    wings: Option<CSGMesh>,
+    breast_patch: Option<CSGMesh>,
+    wing_bars: Option<CSGMesh>,
+}
+
+fn generate_head_parts(input: &BirdGenInputs) -> HeadParts {
     // skull base for head
     let skull: CSGMesh = CSGMesh::sphere(
         input.head_size as f64 / 2.0,
@@ -195,39 +1662,58 @@ pub fn generate_bird_head_mesh(input: &BirdGenInputs) -> Mesh {
     info!("Skull done");
     // beak
     info!("Making the beak");
-    let mut beak_skeleton: CSGMesh = CSGMesh::cylinder(
-        if input.beak_width > 0.0 {
-            input.beak_width as f64
-        } else {
-            NONZERO_THICKNESS
-        },
-        NONZERO_THICKNESS,
-        SPHERE_SEGMENTS / 4, // way less resolution since we're conna covnex hull it
-        None,
-    )
-    .scale(input.beak_roundness as f64 / 100.0, 1.0, 1.0)
-    .translate(
-        (-input.beak_length - input.head_size / 2.0) as f64,
-        0.0,
-        0.0,
-    )
-    .rotate(0.0, 15.0, 0.0)
-    .union(&skull.clone());
-    beak_skeleton.renormalize();
-    info!("Beak skelton done");
-    let mut beak = beak_skeleton.convex_hull().scale(
-        1.0,
-        input.beak_size as f64 / 100.0,
-        input.beak_size as f64 / 100.0,
-    );
+    let beak_scale = input.beak_size as f64 / 100.0;
+    let beak_hulls: Vec<CSGMesh> = if input.beak_shape == BeakShape::Straight {
+        let mut beak_skeleton: CSGMesh = CSGMesh::cylinder(
+            beak_tip_width(input),
+            NONZERO_THICKNESS,
+            SPHERE_SEGMENTS / 4, // way less resolution since we're conna covnex hull it
+            None,
+        )
+        .scale(input.beak_roundness as f64 / 100.0, 1.0, 1.0)
+        .translate(
+            (-input.beak_length - input.head_size / 2.0) as f64,
+            0.0,
+            0.0,
+        )
+        .rotate(0.0, BEAK_TILT, 0.0)
+        .union(&skull.clone());
+        beak_skeleton.renormalize();
+        info!("Beak skelton done");
+        vec![beak_skeleton.convex_hull()]
+    } else {
+        generate_lofted_beak(&skull, input)
+    }
+    .iter()
+    .map(|hull| hull.scale(1.0, beak_scale, beak_scale))
+    .collect();
+    let mut beak_hull = beak_hulls[1..]
+        .iter()
+        .fold(beak_hulls[0].clone(), |beak, hull| beak.union(hull));
+    beak_hull.renormalize();
+    // the skull gets squished the same way as the hull, so the beak is whatever sticks out of it
+    let mut head = skull.scale(1.0, beak_scale, beak_scale);
+    let mut beak = beak_hull.difference(&head);
     beak.renormalize();
-    // guess what, head is the beak now
-    let mut head = beak;
+
+    // the head for the SDF body, in head coordinates like everything else here
+    let skull_radius = input.head_size as f64 / 2.0;
+    let mut head_sdf = vec![Sdf::Ellipsoid {
+        center: DVec3::ZERO,
+        radii: DVec3::new(
+            skull_radius,
+            skull_radius * beak_scale,
+            skull_radius * beak_scale,
+        ),
+    }];
+    let mut head_sdf_cuts = Vec::new();
 
     // eyes
-    if input.eye_size > 0.0 {
-        for y in [-1.0, 1.0] {
-            info!("Making eye");
+    let mut eyes = None;
+    let mut pupils = None;
+    match input.eye_style {
+        EyeStyle::Bump if input.eye_size > 0.0 => {
+            info!("Making eyes");
             let eye: CSGMesh = CSGMesh::sphere(
                 input.eye_size as f64 / 2.0,
                 // half resolution sphere compared to skull
@@ -240,38 +1726,255 @@ pub fn generate_bird_head_mesh(input: &BirdGenInputs) -> Mesh {
                 0.0,
                 0.0,
                 (input.head_size / 2.0 - input.eye_size / 8.0) as f64,
-            )
-            .rotate(50.0, -40.0, 0.0);
-            // .scale(1.0, y, 1.0);
-            info!("Put eye on head");
-            if y == -1.0 {
-                // flip one eye across y plane
-                let plane_y = Plane::from_normal([0.0, 1.0, 0.0].into(), 0.0);
-                head = head.union(&eye.mirror(plane_y));
-            } else {
-                head = head.union(&eye);
-            }
-            // important to do after unions to make sure the mesh looks nice
-            // (i think lol)
+            );
+            // only the bit bulging out of the skull, so the eyes are their own part
+            let mut eye_bumps = both_eyes(&aim_eye(&eye, input)).difference(&head);
+            eye_bumps.renormalize();
+            eyes = Some(eye_bumps);
+        }
+        EyeStyle::Socket => {
+            info!("Making eye sockets");
+            // flat bottomed pocket, half a bead deep, poking out past the skull so it cuts cleanly
+            let bead_radius = input.eye_bead_size as f64 / 2.0;
+            let socket = CSGMesh::cylinder(bead_radius, bead_radius * 2.0, SPHERE_SEGMENTS, None)
+                .translate(0.0, 0.0, input.head_size as f64 / 2.0 - bead_radius);
+            let sockets = both_eyes(&aim_eye(&socket, input));
+            head_sdf_cuts
+                .extend(eye_sides(&aim_eye(&socket, input)).map(|side| Sdf::convex(&side, 0.0)));
+            head = head.difference(&sockets);
             head.renormalize();
+            // big beads can reach into the beak
+            beak = beak.difference(&sockets);
+            beak.renormalize();
+        }
+        EyeStyle::Domed if input.eye_size > 0.0 => {
+            info!("Making eye domes");
+            let eye_radius = input.eye_size as f64 / 2.0;
+            let dome = CSGMesh::sphere(
+                eye_radius,
+                SPHERE_SEGMENTS / 2 + 2,
+                SPHERE_STACKS / 2 + 2,
+                None,
+            )
+            .scale(1.0, 1.0, EYE_DOME_HEIGHT_RATIO)
+            .translate(0.0, 0.0, input.head_size as f64 / 2.0 - eye_radius / 4.0);
+            // leave room for the pupils, they get printed as their own part
+            let eye_pupils = generate_pupils(input);
+            let mut domes = both_eyes(&aim_eye(&dome, input))
+                .difference(&head)
+                .difference(&eye_pupils);
+            domes.renormalize();
+            eyes = Some(domes);
+            pupils = Some(eye_pupils);
         }
+        _ => {}
     }
 
-    let mut head_in_place = head
-        .rotate(0.0, input.head_pitch as f64, input.head_yaw as f64)
-        .translate(
+    // crest, added before the head gets rotated so it follows the pitch and yaw
+    if input.crest_height > 0.0 {
+        info!("Making crest");
+        let plumes = generate_crest_plumes(input);
+        head = plumes.iter().fold(head, |head, plume| head.union(plume));
+        head.renormalize();
+        head_sdf.extend(plumes.iter().map(|plume| Sdf::convex(plume, 0.0)));
+    }
+
+    // markings, the mask goes first so the cheeks don't end up under it
+    let mut face_mask = None;
+    if input.face_mask > 0.0 {
+        info!("Painting face mask");
+        let mask = generate_face_mask(input);
+        face_mask = paint_region(&mut head, &mask);
+        head_sdf_cuts.push(Sdf::convex(&mask, 0.0));
+    }
+    let mut cheek_spots = None;
+    if input.cheek_spots > 0.0 {
+        info!("Painting cheek spots");
+        let spot = generate_cheek_spot(input);
+        cheek_spots = paint_region(&mut head, &both_eyes(&spot));
+        head_sdf_cuts.extend(eye_sides(&spot).map(|side| Sdf::convex(&side, 0.0)));
+    }
+
+    let place = |part: &CSGMesh| {
+        let mut part_in_place = place_head(part, input);
+        part_in_place.renormalize();
+        part_in_place
+    };
+    let head_in_place = place(&head).subdivide_triangles(std::num::NonZero::<u32>::new(1).unwrap());
+    // the beak used to be part of the head, so it gets the same subdivision
+    let beak_in_place = place(&beak).subdivide_triangles(std::num::NonZero::<u32>::new(1).unwrap());
+
+    // same moves as `place_head`, the scale is even so it can go first
+    let place_sdf = |inner: Sdf| Sdf::Transformed {
+        inner: Box::new(inner),
+        scale: DVec3::splat(HEAD_SCALE),
+        rotation: DQuat::from_rotation_z((input.head_yaw as f64).to_radians())
+            * DQuat::from_rotation_y((input.head_pitch as f64).to_radians()),
+        translation: DVec3::new(
             0.0,
             input.head_lateral_offset as f64,
             input.head_level as f64,
-        )
-        .scale(1.1, 1.1, 1.1);
-    head_in_place.renormalize();
-    head_in_place.subdivide_triangles(std::num::NonZero::<u32>::new(1).unwrap());
-    // add the x axis rotation to account for y up world we're rocking with in bevy
-    head_in_place.rotate(-90.0, 180.0, 0.0).to_bevy_mesh()
-}
+        ) * HEAD_SCALE,
+    };
 
-pub fn generate_bird_body_mesh(input: &BirdGenInputs) -> Mesh {
+    HeadParts {
+        head: head_in_place,
+        beak: beak_in_place,
+        beak_hulls: beak_hulls.iter().map(place).collect(),
+        sdf: place_sdf(Sdf::SmoothUnion {
+            parts: head_sdf,
+            radius: 0.0,
+        }),
+        sdf_cuts: head_sdf_cuts.into_iter().map(place_sdf).collect(),
+        eyes: eyes.as_ref().map(place),
+        pupils: pupils.as_ref().map(place),
+        cheek_spots: cheek_spots.as_ref().map(place),
+        face_mask: face_mask.as_ref().map(place),
+    }
+}
+
+// Move the head (built around the origin) into place on the body
+fn place_head(head: &CSGMesh, input: &BirdGenInputs) -> CSGMesh {
+    head.rotate(0.0, input.head_pitch as f64, input.head_yaw as f64)
+        .translate(
+            0.0,
+            input.head_lateral_offset as f64,
+            input.head_level as f64,
+        )
+        .scale(HEAD_SCALE, HEAD_SCALE, HEAD_SCALE)
+}
+
+fn beak_tip_width(input: &BirdGenInputs) -> f64 {
+    if input.beak_width > 0.0 {
+        input.beak_width as f64
+    } else {
+        NONZERO_THICKNESS
+    }
+}
+
+// Beak for the shapes that aren't a straight cone: cross sections swept along a bending
+// centerline (from the middle of the skull out to the tip), each pair of neighbouring
+// sections hulled together. The first link hulls with the skull, like the straight beak does.
+// Gives back the hulls, tilted into place, for unioning together.
+fn generate_lofted_beak(skull: &CSGMesh, input: &BirdGenInputs) -> Vec<CSGMesh> {
+    let skull_radius = input.head_size as f64 / 2.0;
+    let tip_width = beak_tip_width(input);
+    let spine_length = input.beak_length as f64 + skull_radius;
+    let curve = input.beak_curve as f64;
+    let hook = BEAK_HOOK_ANGLE * input.beak_droop as f64 / 100.0;
+
+    // how far along the centerline has bent down (degrees) at `along` (0 at the skull, 1 at the tip)
+    let bend = |along: f64| match input.beak_shape {
+        // mostly straight, then curling over hard at the end
+        BeakShape::Hooked => curve * along + hook * along.powi(3),
+        // even curve all the way, a bit of droop right at the tip
+        BeakShape::Curved => curve * along + hook * along.powi(6),
+        // ducks are pretty flat, the droop is the little nail at the tip
+        _ => curve * 0.3 * along + hook * 0.3 * along.powi(8),
+    };
+    // half height and half width of the cross section at `along`
+    let taper =
+        |along: f64, power: f64| skull_radius + (tip_width - skull_radius) * along.powf(power);
+    let section_size = |along: f64| match input.beak_shape {
+        // deep all the way along, and on the narrow side
+        BeakShape::Hooked => {
+            let half_height = taper(along, 1.6);
+            (half_height, half_height * 0.75)
+        }
+        // thins out quickly into a long probe
+        BeakShape::Curved => {
+            let radius = taper(along, 0.4);
+            (radius, radius)
+        }
+        // flat, staying wide out to a rounded tip
+        _ => {
+            let half_height = (taper(along, 0.8) * 0.4).max(NONZERO_THICKNESS);
+            let half_width =
+                skull_radius * 0.8 + (skull_radius * 0.4 + tip_width - skull_radius * 0.8) * along;
+            (half_height, half_width)
+        }
+    };
+
+    // walk along the centerline, a few steps per section
+    let steps_per_section = 4;
+    let step_count = BEAK_LOFT_SECTIONS * steps_per_section;
+    let step_length = spine_length / step_count as f64;
+    let mut position = DVec3::ZERO;
+    let mut sections = Vec::new();
+    for step in 1..=step_count {
+        let along = step as f64 / step_count as f64;
+        let angle = bend(along - 0.5 / step_count as f64).to_radians();
+        position += DVec3::new(-angle.cos(), 0.0, -angle.sin()) * step_length;
+        if step % steps_per_section != 0 {
+            continue;
+        }
+
+        let (half_height, half_width) = section_size(along);
+        let angle = bend(along);
+        let section = if step == step_count {
+            // round the tip off the same way the straight beak does
+            let tip_length =
+                (tip_width * input.beak_roundness as f64 / 100.0).max(NONZERO_THICKNESS);
+            CSGMesh::sphere(1.0, SPHERE_SEGMENTS / 2, SPHERE_STACKS / 4, None).scale(
+                half_height,
+                half_width,
+                tip_length,
+            )
+        } else {
+            CSGMesh::cylinder(1.0, NONZERO_THICKNESS, SPHERE_SEGMENTS / 2, None)
+                .translate(0.0, 0.0, -NONZERO_THICKNESS / 2.0)
+                .scale(half_height, half_width, 1.0)
+        };
+        // flat in x/y, turn it so it faces along the centerline (the local x ends up pointing "up")
+        sections.push(
+            section
+                .rotate(0.0, -(90.0 + angle), 0.0)
+                .translate(position.x, position.y, position.z),
+        );
+    }
+
+    std::iter::once(skull.union(&sections[0]).convex_hull())
+        .chain(
+            sections
+                .windows(2)
+                .map(|pair| pair[0].union(&pair[1]).convex_hull()),
+        )
+        .map(|hull| hull.rotate(0.0, BEAK_TILT, 0.0))
+        .collect()
+}
+
+// Swing something sitting on top of the skull (+z) around to where the eye goes
+fn aim_eye(eye_part: &CSGMesh, input: &BirdGenInputs) -> CSGMesh {
+    eye_part.rotate(input.eye_spacing as f64, -input.eye_angle as f64, 0.0)
+}
+
+// Pair of something aimed with `aim_eye`, mirrored across the y plane for the other side
+fn both_eyes(eye_part: &CSGMesh) -> CSGMesh {
+    let [left, right] = eye_sides(eye_part);
+    left.union(&right)
+}
+
+// Same pair as `both_eyes`, kept apart (each side stays convex if the part was)
+fn eye_sides(eye_part: &CSGMesh) -> [CSGMesh; 2] {
+    let plane_y = Plane::from_normal([0.0, 1.0, 0.0].into(), 0.0);
+    [eye_part.clone(), eye_part.mirror(plane_y)]
+}
+
+// Pupils sunk into the top of the eye domes, in head coordinates
+fn generate_pupils(input: &BirdGenInputs) -> CSGMesh {
+    let eye_radius = input.eye_size as f64 / 2.0;
+    let pupil_radius = eye_radius * PUPIL_RATIO;
+    let dome_top =
+        input.head_size as f64 / 2.0 - eye_radius / 4.0 + eye_radius * EYE_DOME_HEIGHT_RATIO;
+    let pupil = CSGMesh::sphere(pupil_radius, SPHERE_SEGMENTS / 2, SPHERE_STACKS / 2, None)
+        .scale(1.0, 1.0, 0.5)
+        .translate(0.0, 0.0, dome_top - pupil_radius * 0.3);
+    both_eyes(&aim_eye(&pupil, input))
+}
+
+// Body and wings in the OpenSCAD style z up coordinates, standing on whatever the stand option says
+// (takes the head over when it's blended in or filleted on, unless it's split off)
+fn generate_body_parts(input: &BirdGenInputs, head: &mut HeadParts) -> BodyParts {
     info!("Body step 1, neck and chest");
     let neck = CSGMesh::sphere(
         input.head_size as f64 / 2.0,
@@ -296,7 +1999,6 @@ pub fn generate_bird_body_mesh(input: &BirdGenInputs) -> Mesh {
         1.0,
     )
     .translate(input.head_to_belly as f64, 0.0, 0.0);
-    let mut body = neck.union(&chest).convex_hull();
     info!("Body step 2, bottom");
     let bottom = CSGMesh::sphere(
         input.bottom_size as f64 / 2.0,
@@ -309,53 +2011,1441 @@ pub fn generate_bird_body_mesh(input: &BirdGenInputs) -> Mesh {
         0.0,
         0.0,
     );
-    let body_plus_bottom = body.union(&bottom).convex_hull();
-    body = body_plus_bottom;
     info!("Body step 3, tail");
-    let tail = CSGMesh::cylinder(
-        input.tail_width as f64,
-        NONZERO_THICKNESS,
-        SPHERE_SEGMENTS + 1,
-        None,
-    )
-    .scale(input.tail_roundness as f64 / 100.0, 1.0, 1.0)
-    .translate(input.tail_length as f64, 0.0, 0.0)
-    .rotate(0.0, -input.tail_pitch as f64, input.tail_yaw as f64)
-    .translate(
+    let tail_pitch = if input.tail_style == TailStyle::Wren {
+        input.tail_cock
+    } else {
+        input.tail_pitch
+    };
+    let place_tail = |tail_part: &CSGMesh| {
+        tail_part
+            .rotate(0.0, -tail_pitch as f64, input.tail_yaw as f64)
+            .translate(
+                (input.head_to_belly + input.belly_to_bottom) as f64,
+                0.0,
+                0.0,
+            )
+    };
+    // the classic tail is just a disc for the body to hull into,
+    // the other styles hull into a smaller one and get their feathers stuck on after
+    let tail = place_tail(&if input.tail_style == TailStyle::Classic {
+        CSGMesh::cylinder(
+            input.tail_width as f64,
+            NONZERO_THICKNESS,
+            SPHERE_SEGMENTS + 1,
+            None,
+        )
+        .scale(input.tail_roundness as f64 / 100.0, 1.0, 1.0)
+        .translate(input.tail_length as f64, 0.0, 0.0)
+    } else {
+        CSGMesh::cylinder(
+            input.tail_width as f64 * TAIL_ROOT_RATIO,
+            NONZERO_THICKNESS,
+            SPHERE_SEGMENTS + 1,
+            None,
+        )
+        .translate(input.tail_length as f64 * TAIL_ROOT_RATIO, 0.0, 0.0)
+    });
+    let tail_feathers: Vec<CSGMesh> = generate_tail_feathers(input)
+        .iter()
+        .map(place_tail)
+        .collect();
+    // the container cavity follows everything but the neck, so it stays clear of the head
+    let container_hull = if input.container != ContainerShape::Off {
+        Some(chest.union(&bottom).union(&tail).convex_hull())
+    } else {
+        None
+    };
+    // the whistle chamber stays out of the tail too, the windway runs through there
+    let whistle_hull = if input.whistle {
+        Some(chest.union(&bottom).convex_hull())
+    } else {
+        None
+    };
+    // what the bird stands on and the height everything gets cut off at, worked out up front
+    // since the SDF body gets cut before it's polygonized
+    let (stand, stand_height) = match input.stand {
+        BirdStand::Legs => {
+            let (legs, sole_height) = generate_legs(input);
+            (Some(legs), Some(sole_height))
+        }
+        BirdStand::Perch => {
+            let (perch, base_height) = generate_perch(input);
+            (Some(perch), Some(base_height))
+        }
+        BirdStand::Belly if input.base_flat > -100.0 => (None, Some(base_flat_height(input))),
+        BirdStand::Belly => (None, None),
+    };
+    let sdf = input.geometry == GeometryBackend::Sdf;
+    let (mut body, mut breast_patch) = if sdf {
+        // the UI keeps this to the hull body, there's nothing to loft with
+        info!("Blending body from distance fields");
+        let (body, breast_patch) =
+            generate_sdf_body(input, tail_pitch, head, &tail_feathers, stand_height);
+        // one continuous surface from the body up over the head, unless the head has to come off
+        if !input.split_head {
+            head.head = CSGMesh::new();
+        }
+        (body, breast_patch)
+    } else {
+        let mut body = match input.body_style {
+            BodyStyle::Hull => {
+                info!("Hulling body");
+                let body = neck.union(&chest).convex_hull();
+                let body_plus_bottom = body.union(&bottom).convex_hull();
+                body_plus_bottom.union(&tail).convex_hull()
+            }
+            BodyStyle::Lofted => {
+                info!("Lofting body");
+                generate_lofted_body(input, tail_pitch)
+            }
+        };
+        body = tail_feathers
+            .iter()
+            .fold(body, |body, feather| body.union(feather));
+        if input.neck_fillet > 0.0 {
+            info!("Filleting neck");
+            body = body.union(&generate_neck_fillet(input, head));
+            // same continuous surface as the SDF body gets
+            if !input.split_head {
+                body = body.union(&std::mem::replace(&mut head.head, CSGMesh::new()));
+            }
+        }
+        (body, None)
+    };
+    body.renormalize();
+
+    let mut wings = None;
+    if input.wing_length > 0.0 {
+        info!("Body step 4, wings");
+        let wing = generate_wing(input);
+        // same trick as the eyes, mirror instead of scaling by -1
+        let plane_y = Plane::from_normal([0.0, 1.0, 0.0].into(), 0.0);
+        // the wings are their own part, so only keep what sticks out of the body
+        let mut both_wings = wing.union(&wing.mirror(plane_y)).difference(&body);
+        both_wings.renormalize();
+        wings = Some(both_wings);
+    }
+    info!("Body done");
+
+    // height of the flat bottom the bird ends up standing on (if there is one)
+    let mut floor_height = stand_height;
+    if let (Some(stand), Some(height)) = (stand, stand_height) {
+        if input.stand == BirdStand::Legs {
+            info!("Adding legs");
+        } else {
+            info!("Adding perch");
+        }
+        // the SDF body is already cut, only the stand needs it
+        body = if sdf {
+            body.union(&cut_below(&stand, height, input))
+        } else {
+            cut_below(&body.union(&stand), height, input)
+        };
+        wings = wings.map(|wings| cut_below(&wings, height, input));
+    } else if stand_height.is_some() {
+        info!("Flattening base");
+        if !sdf {
+            body = flatten_base(&body, input);
+        }
+        wings = wings.map(|wings| flatten_base(&wings, input));
+    }
+    body.renormalize();
+
+    if input.name_plate != NamePlate::Off && !input.name_text.trim().is_empty() {
+        info!("Adding name plate");
+        let (body_with_plate, new_floor_height) = add_name_plate(&body, floor_height, input);
+        body = body_with_plate;
+        floor_height = new_floor_height;
+        body.renormalize();
+    }
+
+    body = add_mounting(&body, floor_height, input);
+
+    if let Some(container_hull) = container_hull {
+        info!("Hollowing out container");
+        match generate_container_cavity(&container_hull, floor_height, input) {
+            Some(cavity) => {
+                body = body.difference(&cavity);
+                body.renormalize();
+                // the SDF body comes with its patch already split off
+                breast_patch = breast_patch.map(|patch| patch.difference(&cavity));
+            }
+            None => info!("Walls too thick for the body, no room for a cavity"),
+        }
+    } else if let Some(whistle_hull) = whistle_hull {
+        info!("Carving whistle");
+        match generate_whistle(&body, &whistle_hull, floor_height, input) {
+            Some((mouthpiece, air_path)) => {
+                body = body.union(&mouthpiece).difference(&air_path);
+                body.renormalize();
+                breast_patch = breast_patch.map(|patch| patch.difference(&air_path));
+                // the wings hug the body, keep them out of the window
+                wings = wings.map(|wings| wings.difference(&air_path));
+            }
+            None => info!("Body too small for a whistle chamber"),
+        }
+    }
+    if let Some(wings) = wings.as_mut() {
+        wings.renormalize();
+    }
+
+    // markings last, so they follow all the cuts above
+    if !sdf && input.breast_patch > 0.0 {
+        info!("Painting breast patch");
+        breast_patch = paint_region(&mut body, &generate_breast_patch(input));
+    }
+    let mut wing_bars = None;
+    if let Some(wings) = wings.as_mut()
+        && input.wing_bars >= 1.0
+    {
+        info!("Painting wing bars");
+        wing_bars = paint_region(wings, &generate_wing_bars(input));
+    }
+    BodyParts {
+        body,
+        wings,
+        breast_patch,
+        wing_bars,
+    }
+}
+
+// Cross section of the lofted body: a half ellipse on top and one underneath, sharing the width
+#[derive(Clone, Copy)]
+struct LoftSection {
+    center: DVec3,
+    top: f64,
+    bottom: f64,
+    half_width: f64,
+}
+
+// Body lofted along a spine running from the middle of the head, down the neck, through the chest
+// and bottom and out to the tail (same spots the sphere hull uses). The key sections get smoothed
+// into a Catmull-Rom spline, sampled into rings, and the rings stitched into a closed mesh.
+fn generate_lofted_body(input: &BirdGenInputs, tail_pitch: f32) -> CSGMesh {
+    let head_center = DVec3::new(
+        0.0,
+        input.head_lateral_offset as f64,
+        input.head_level as f64,
+    );
+    let head_radius = input.head_size as f64 / 2.0;
+    let round = |center: DVec3, radius: f64| LoftSection {
+        center,
+        top: radius,
+        bottom: radius,
+        half_width: radius,
+    };
+
+    let mut keys = vec![round(head_center, head_radius * 0.9)];
+    let chest_center = DVec3::new(input.head_to_belly as f64, 0.0, 0.0);
+    if input.neck_length > 0.0 {
+        let neck_direction = (chest_center - head_center).normalize_or_zero();
+        keys.push(round(
+            head_center + neck_direction * input.neck_length as f64,
+            head_radius * NECK_RATIO,
+        ));
+    }
+
+    // the chest is two sections, belly_length apart, so it stretches like the squashed sphere does
+    let chest_radius = input.belly_size as f64 / 2.0;
+    let chest_stretch = (input.belly_length as f64 / 4.0).min(input.belly_to_bottom as f64 / 2.0);
+    let arch = 1.0 + input.back_arch as f64 / 100.0;
+    let sag = 1.0 + input.belly_sag as f64 / 100.0;
+    for offset in [-chest_stretch, chest_stretch] {
+        keys.push(LoftSection {
+            center: chest_center + DVec3::X * offset,
+            top: chest_radius * arch,
+            bottom: chest_radius * sag,
+            half_width: chest_radius * input.belly_fat as f64 / 100.0,
+        });
+    }
+    // bottom only gets half the arch and sag, it's where the curves settle back down
+    let bottom_center = DVec3::new(
         (input.head_to_belly + input.belly_to_bottom) as f64,
         0.0,
         0.0,
     );
-    let body_plus_tail = body.union(&tail).convex_hull();
-    body = body_plus_tail;
+    let bottom_radius = input.bottom_size as f64 / 2.0;
+    keys.push(LoftSection {
+        center: bottom_center,
+        top: bottom_radius * (1.0 + (arch - 1.0) / 2.0),
+        bottom: bottom_radius * (1.0 + (sag - 1.0) / 2.0),
+        half_width: bottom_radius,
+    });
+
+    // tail end, thin and as wide as the tail
+    let (tail_length, tail_width) = tail_hull_size(input);
+    let (pitch, yaw) = (
+        (tail_pitch as f64).to_radians(),
+        (input.tail_yaw as f64).to_radians(),
+    );
+    let tail_direction = DVec3::new(
+        pitch.cos() * yaw.cos(),
+        pitch.cos() * yaw.sin(),
+        pitch.sin(),
+    );
+    keys.push(LoftSection {
+        center: bottom_center + tail_direction * tail_length,
+        top: NONZERO_THICKNESS,
+        bottom: NONZERO_THICKNESS,
+        half_width: tail_width,
+    });
+
+    // sample the spline, ends doubled up so it goes through the first and last key
+    let mut samples = Vec::new();
+    for span in 0..keys.len() - 1 {
+        let [a, b, c, d] = [
+            keys[span.saturating_sub(1)],
+            keys[span],
+            keys[span + 1],
+            keys[(span + 2).min(keys.len() - 1)],
+        ];
+        let last_span = span == keys.len() - 2;
+        for sample in 0..LOFT_SPAN_SAMPLES + usize::from(last_span) {
+            let t = sample as f64 / LOFT_SPAN_SAMPLES as f64;
+            samples.push(LoftSection {
+                center: catmull_rom([a.center, b.center, c.center, d.center], t),
+                top: catmull_rom([a.top, b.top, c.top, d.top], t).max(NONZERO_THICKNESS),
+                bottom: catmull_rom([a.bottom, b.bottom, c.bottom, d.bottom], t)
+                    .max(NONZERO_THICKNESS),
+                half_width: catmull_rom(
+                    [a.half_width, b.half_width, c.half_width, d.half_width],
+                    t,
+                )
+                .max(NONZERO_THICKNESS),
+            });
+        }
+    }
+
+    // rings standing square to the spine, with the sides kept as close to sideways as the spine
+    // allows (a spine heading sideways falls back to keeping the top up)
+    let rings: Vec<Vec<DVec3>> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, section)| {
+            let before = samples[i.saturating_sub(1)].center;
+            let after = samples[(i + 1).min(samples.len() - 1)].center;
+            let along = (after - before).try_normalize().unwrap_or(DVec3::X);
+            let reference = if along.dot(DVec3::Y).abs() < 0.9 {
+                DVec3::Y
+            } else {
+                DVec3::Z
+            };
+            let side = (reference - along * along.dot(reference)).normalize();
+            let up = along.cross(side);
+            let offsets: Vec<DVec3> = (0..LOFT_RING_SEGMENTS)
+                .map(|j| {
+                    let angle = std::f64::consts::TAU * j as f64 / LOFT_RING_SEGMENTS as f64;
+                    let height = if angle.cos() >= 0.0 {
+                        section.top
+                    } else {
+                        section.bottom
+                    };
+                    up * height * angle.cos() + side * section.half_width * angle.sin()
+                })
+                .collect();
+            // on a tight bend the inside of the ring can't reach past the middle of the bend,
+            // or it'd cross over its neighbours and turn the body inside out
+            let shrink = bend(before, section.center, after)
+                .and_then(|(radius, inwards)| {
+                    let reach = offsets
+                        .iter()
+                        .map(|offset| offset.dot(inwards))
+                        .fold(0.0, f64::max);
+                    (reach > radius * LOFT_BEND_LIMIT).then(|| radius * LOFT_BEND_LIMIT / reach)
+                })
+                .unwrap_or(1.0);
+            offsets
+                .iter()
+                .map(|offset| section.center + *offset * shrink)
+                .collect()
+        })
+        .collect();
+
+    let mut faces: Vec<Vec<DVec3>> = Vec::new();
+    for pair in rings.windows(2) {
+        for j in 0..LOFT_RING_SEGMENTS {
+            let next = (j + 1) % LOFT_RING_SEGMENTS;
+            faces.push(vec![pair[0][j], pair[0][next], pair[1][next]]);
+            faces.push(vec![pair[0][j], pair[1][next], pair[1][j]]);
+        }
+    }
+    // flat caps on both ends, the first one wound backwards so it faces the other way to the last
+    faces.push(rings[0].iter().rev().copied().collect());
+    faces.push(rings[rings.len() - 1].clone());
+
+    // whichever way round the rings ended up winding, make the faces point out
+    let signed_volume: f64 = faces
+        .iter()
+        .flat_map(|face| (1..face.len() - 1).map(move |i| face[0].dot(face[i].cross(face[i + 1]))))
+        .sum();
+    if signed_volume < 0.0 {
+        faces.iter_mut().for_each(|face| face.reverse());
+    }
+
+    let polygons: Vec<Polygon<()>> = faces
+        .iter()
+        .map(|face| {
+            let normal = (face[1] - face[0])
+                .cross(face[2] - face[0])
+                .normalize_or_zero();
+            Polygon::new(
+                face.iter()
+                    .map(|point| {
+                        Vertex::new(
+                            [point.x, point.y, point.z].into(),
+                            [normal.x, normal.y, normal.z].into(),
+                        )
+                    })
+                    .collect(),
+                None,
+            )
+        })
+        .collect();
+    let mut body = CSGMesh::from_polygons(&polygons, None);
     body.renormalize();
-    info!("Body done");
+    body
+}
 
-    if input.base_flat > -100.0 {
-        info!("Flattening base");
-        let total_len =
-            input.beak_length + input.head_to_belly + input.belly_to_bottom + input.tail_length;
+// Radius of the circle through three points along the spine, and which way the middle one has to
+// go to get to its centre. None if they're in a straight line.
+fn bend(before: DVec3, at: DVec3, after: DVec3) -> Option<(f64, DVec3)> {
+    let twice_area = (at - before).cross(after - before).length();
+    if twice_area < 1e-9 {
+        return None;
+    }
+    let radius = (at - before).length() * (after - at).length() * (after - before).length()
+        / (2.0 * twice_area);
+    let inwards = ((before + after) / 2.0 - at).try_normalize()?;
+    Some((radius, inwards))
+}
+
+// Length and width of the tail the body hulls into,
+// the root disc the feathers come out of when the tail isn't the classic one
+fn tail_hull_size(input: &BirdGenInputs) -> (f64, f64) {
+    if input.tail_style == TailStyle::Classic {
+        (input.tail_length as f64, input.tail_width as f64)
+    } else {
+        (
+            input.tail_length as f64 * TAIL_ROOT_RATIO,
+            input.tail_width as f64 * TAIL_ROOT_RATIO,
+        )
+    }
+}
+
+// The sphere hull body rebuilt as distance fields: same neck, chest, bottom and tail, with round
+// cones standing in for the hulls between them, plus the tail feathers and (unless it's split off)
+// the head, all smooth blended. The tail is a round cone out to an ellipse stretched by the tail
+// roundness, squashed flat. The eye sockets, head markings and the flat bottom at `floor_height`
+// get cut in the field too, and the breast patch split off of it, before meshing with marching
+// tetrahedra, so the body and patch come out watertight. Also returns the breast patch.
+fn generate_sdf_body(
+    input: &BirdGenInputs,
+    tail_pitch: f32,
+    head: &HeadParts,
+    tail_feathers: &[CSGMesh],
+    floor_height: Option<f64>,
+) -> (CSGMesh, Option<CSGMesh>) {
+    let neck_center = DVec3::new(
+        0.0,
+        input.head_lateral_offset as f64,
+        input.head_level as f64,
+    );
+    let neck_radius = input.head_size as f64 / 2.0;
+    let chest_center = DVec3::new(input.head_to_belly as f64, 0.0, 0.0);
+    let chest_radii = DVec3::new(
+        input.belly_length as f64 / 2.0,
+        input.belly_size as f64 / 2.0 * input.belly_fat as f64 / 100.0,
+        input.belly_size as f64 / 2.0,
+    );
+    // round cones end in spheres, the narrower side of the chest keeps them inside it
+    let chest_radius = chest_radii.y.min(chest_radii.z);
+    let bottom_center = DVec3::new(
+        (input.head_to_belly + input.belly_to_bottom) as f64,
+        0.0,
+        0.0,
+    );
+    let bottom_radius = input.bottom_size as f64 / 2.0;
+    let (tail_length, tail_width) = tail_hull_size(input);
+    let tail_roundness = if input.tail_style == TailStyle::Classic {
+        input.tail_roundness as f64 / 100.0
+    } else {
+        1.0
+    };
+
+    let round_cone = |from: DVec3, from_radius: f64, to: DVec3, to_radius: f64| Sdf::RoundCone {
+        from,
+        to,
+        from_radius,
+        to_radius,
+    };
+    let tail = Sdf::Transformed {
+        inner: Box::new(Sdf::SmoothUnion {
+            parts: vec![
+                round_cone(
+                    DVec3::ZERO,
+                    bottom_radius,
+                    DVec3::X * tail_length,
+                    tail_width * tail_roundness.min(1.0),
+                ),
+                Sdf::Ellipsoid {
+                    center: DVec3::X * tail_length,
+                    radii: DVec3::new(tail_width * tail_roundness, tail_width, tail_width),
+                },
+            ],
+            radius: 0.0,
+        }),
+        scale: DVec3::new(1.0, 1.0, SDF_TAIL_FLATTEN),
+        rotation: DQuat::from_rotation_z((input.tail_yaw as f64).to_radians())
+            * DQuat::from_rotation_y(-(tail_pitch as f64).to_radians()),
+        translation: bottom_center,
+    };
+    let mut parts = vec![
+        Sdf::Sphere {
+            center: neck_center,
+            radius: neck_radius,
+        },
+        round_cone(neck_center, neck_radius, chest_center, chest_radius),
+        Sdf::Ellipsoid {
+            center: chest_center,
+            radii: chest_radii,
+        },
+        round_cone(chest_center, chest_radius, bottom_center, bottom_radius),
+        Sdf::Sphere {
+            center: bottom_center,
+            radius: bottom_radius,
+        },
+        // the feathers overlap each other and the tail, they just get a plain union
+        Sdf::SmoothUnion {
+            parts: tail_feathers
+                .iter()
+                .map(|feather| Sdf::convex(feather, 0.0))
+                .chain([tail])
+                .collect(),
+            radius: 0.0,
+        },
+    ];
+    let mut cuts = Vec::new();
+    if !input.split_head {
+        parts.push(head.sdf.clone());
+        // only where they're in the head, the face mask band runs on out past it
+        cuts.extend(head.sdf_cuts.iter().map(|cut| Sdf::Intersection {
+            parts: vec![head.sdf.clone(), cut.clone()],
+        }));
+    }
+    if let Some(floor_height) = floor_height {
+        cuts.push(Sdf::HalfSpace {
+            normal: DVec3::Z,
+            offset: floor_height,
+        });
+    }
+    let body = Sdf::Difference {
+        inner: Box::new(Sdf::SmoothUnion {
+            parts,
+            radius: input.blend_radius as f64,
+        }),
+        cuts,
+    };
+
+    if input.breast_patch <= 0.0 {
+        return (body.polygonize(SDF_GRID_CELLS), None);
+    }
+    info!("Painting breast patch");
+    let patch = Sdf::convex(&generate_breast_patch(input), 0.0);
+    let breast_patch = Sdf::Intersection {
+        parts: vec![body.clone(), patch.clone()],
+    };
+    let body = Sdf::Difference {
+        inner: Box::new(body),
+        cuts: vec![patch],
+    };
+    (
+        body.polygonize(SDF_GRID_CELLS),
+        Some(breast_patch.polygonize(SDF_GRID_CELLS)),
+    )
+}
+
+// Collar for the body that fills the crease where the neck goes into the head, so the body surface
+// runs smoothly up onto the head instead of just stopping at it. The head (the skull and beak hulls
+// as placed) and the neck-to-chest hull get blended as distance fields, and whatever of that isn't
+// head (or anything stuck on it) or too far from the head is the fillet. The blended head sits just
+// inside the real one, so the fillet meets the head's surface without leaving a skin over it.
+fn generate_neck_fillet(input: &BirdGenInputs, head: &HeadParts) -> CSGMesh {
+    let head_radius = input.head_size as f64 / 2.0;
+    let head_center = DVec3::new(
+        0.0,
+        input.head_lateral_offset as f64,
+        input.head_level as f64,
+    );
+    let fillet_radius = input.neck_fillet as f64;
+
+    let skull = Sdf::SmoothUnion {
+        parts: head
+            .beak_hulls
+            .iter()
+            .map(|hull| Sdf::convex(hull, SDF_HEAD_INSET))
+            .collect(),
+        radius: 0.0,
+    };
+    // the narrow side of the chest, like the SDF body, so the cone stays inside the hull
+    let chest_radius = input.belly_size as f64 / 2.0 * (input.belly_fat as f64 / 100.0).min(1.0);
+    let neck = Sdf::RoundCone {
+        from: head_center,
+        to: DVec3::new(input.head_to_belly as f64, 0.0, 0.0),
+        from_radius: head_radius,
+        to_radius: chest_radius,
+    };
+    let blended = Sdf::SmoothUnion {
+        parts: vec![skull, neck],
+        radius: fillet_radius,
+    }
+    .polygonize(SDF_GRID_CELLS);
+
+    // the blend only reaches about the fillet radius past the head
+    let reach = CSGMesh::sphere(
+        (head_radius + fillet_radius) * HEAD_SCALE,
+        SPHERE_SEGMENTS,
+        SPHERE_STACKS,
+        None,
+    )
+    .translate(
+        head_center.x * HEAD_SCALE,
+        head_center.y * HEAD_SCALE,
+        head_center.z * HEAD_SCALE,
+    );
+    let head_pieces = head
+        .beak_hulls
+        .iter()
+        .chain([&head.head, &head.beak])
+        .chain(head.eyes.as_ref())
+        .chain(head.pupils.as_ref())
+        .chain(head.cheek_spots.as_ref())
+        .chain(head.face_mask.as_ref());
+    let mut fillet = head_pieces.fold(blended.intersection(&reach), |fillet, head_piece| {
+        fillet.difference(head_piece)
+    });
+    fillet.renormalize();
+    fillet
+}
+
+// Catmull-Rom spline between `points[1]` and `points[2]`
+fn catmull_rom<T>(points: [T; 4], t: f64) -> T
+where
+    T: Copy
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<f64, Output = T>,
+{
+    let [a, b, c, d] = points;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (b * 2.0
+        + (c - a) * t
+        + (a * 2.0 - b * 5.0 + c * 4.0 - d) * t2
+        + (b * 3.0 - a - c * 3.0 + d) * t3)
+        * 0.5
+}
 
-        // Calculate the cut height (in OpenSCAD's z-axis, which becomes Bevy's y-axis after rotation)
-        let cut_height = (input.belly_size * (-1.5 + input.base_flat / 200.0)) as f64;
+// Feathers for the tail styles that aren't a plain disc, fanning out along +x in the xy plane
+// from the base of the tail, each one a hull (none for the classic tail)
+fn generate_tail_feathers(input: &BirdGenInputs) -> Vec<CSGMesh> {
+    let length = input.tail_length as f64;
+    let width = input.tail_width as f64;
+    let spread = input.tail_spread as f64;
+    match input.tail_style {
+        TailStyle::Classic => Vec::new(),
+        // two long streamers either side of a notch
+        TailStyle::Forked => {
+            let notch_length = length * (1.0 - input.tail_fork as f64 / 100.0);
+            let notch = tail_feather(notch_length, width, width * 0.5, 0.0, input);
+            [-0.25, 0.25]
+                .into_iter()
+                .map(|side| {
+                    tail_feather(length, width * 0.5, NONZERO_THICKNESS, spread * side, input)
+                })
+                .chain([notch])
+                .collect()
+        }
+        // evenly spread feathers, wide enough to overlap a bit at the tips
+        TailStyle::Fan => {
+            let count = input.tail_feathers.round().max(2.0) as usize;
+            let feather_width = width * 2.0 / count as f64 * 1.3;
+            (0..count)
+                .map(|i| {
+                    let angle = spread * (i as f64 / (count - 1) as f64 - 0.5);
+                    tail_feather(length, feather_width * 0.4, feather_width, angle, input)
+                })
+                .collect()
+        }
+        // one long tapering spike
+        TailStyle::Pointed => {
+            let point_length = length * (1.0 + input.tail_point as f64 / 100.0);
+            vec![tail_feather(
+                point_length,
+                width,
+                NONZERO_THICKNESS,
+                0.0,
+                input,
+            )]
+        }
+        // short and stubby, it's the angle that makes it a wren
+        TailStyle::Wren => vec![tail_feather(
+            length * 0.7,
+            width * 0.4,
+            width * 0.8,
+            0.0,
+            input,
+        )],
+    }
+}
 
-        // Create a large cube to subtract from the bottom
-        let cut_box = CSGMesh::cuboid(
-            (total_len * 10.0) as f64,
-            (total_len * 10.0) as f64,
-            input.belly_size as f64,
+// Single flat feather from the origin out along +x (turned by `angle` degrees around z),
+// `root_width` wide at the base and `tip_width` wide at the (tail_roundness) rounded tip
+fn tail_feather(
+    length: f64,
+    root_width: f64,
+    tip_width: f64,
+    angle: f64,
+    input: &BirdGenInputs,
+) -> CSGMesh {
+    let disc = |radius: f64, thickness: f64| {
+        CSGMesh::cylinder(
+            radius.max(NONZERO_THICKNESS),
+            thickness,
+            SPHERE_SEGMENTS / 2,
             None,
         )
-        .translate(0.0, 0.0, cut_height);
+        .translate(0.0, 0.0, -thickness / 2.0)
+    };
+    let root = disc(root_width / 2.0, TAIL_FEATHER_THICKNESS);
+    let tip = disc(tip_width / 2.0, TAIL_FEATHER_THICKNESS / 2.0)
+        .scale(input.tail_roundness as f64 / 100.0, 1.0, 1.0)
+        .translate(length, 0.0, 0.0);
+    let mut feather = root.union(&tip).convex_hull().rotate(0.0, 0.0, angle);
+    feather.renormalize();
+    feather
+}
+
+// Where the base_flat cut leaves the bottom of the bird
+fn base_flat_height(input: &BirdGenInputs) -> f64 {
+    (input.belly_size * (-0.5 + input.base_flat / 200.0)) as f64
+}
+
+// The base_flat cut for birds sitting on their belly
+fn flatten_base(mesh: &CSGMesh, input: &BirdGenInputs) -> CSGMesh {
+    let total_len =
+        input.beak_length + input.head_to_belly + input.belly_to_bottom + input.tail_length;
+
+    // Calculate the cut height (in OpenSCAD's z-axis, which becomes Bevy's y-axis after rotation)
+    let cut_height = base_flat_height(input) - input.belly_size as f64;
+
+    // Create a large cube to subtract from the bottom
+    let cut_box = CSGMesh::cuboid(
+        (total_len * 10.0) as f64,
+        (total_len * 10.0) as f64,
+        input.belly_size as f64,
+        None,
+    )
+    .translate(0.0, 0.0, cut_height);
+
+    mesh.difference(&cut_box)
+}
+
+// Left wing (positive y), built as a hull of three flattened spheres from shoulder to tip
+fn generate_wing(input: &BirdGenInputs) -> CSGMesh {
+    let length = input.wing_length as f64;
+    let width = input.wing_width as f64;
+    // wing laid out along +x, chord along z and the thin side along y
+    let feather_blob = |radius: f64, x: f64, z: f64| {
+        CSGMesh::sphere(radius, SPHERE_SEGMENTS / 2 + 2, SPHERE_STACKS / 2 + 2, None)
+            .scale(1.0, WING_THICKNESS_RATIO, 1.0)
+            .translate(x, 0.0, z)
+    };
+    let shoulder = feather_blob(width / 2.0, 0.0, 0.0);
+    let elbow = feather_blob(width * 0.4, length * 0.45, -width * 0.1);
+    let tip = feather_blob(width / 6.0, length, -width * 0.15);
+    let mut wing = shoulder.union(&elbow).union(&tip).convex_hull();
+    wing.renormalize();
+    place_wing(&wing, input)
+}
+
+// Pose something laid out like the wing (along +x, chord along z) and move it to the shoulder
+fn place_wing(wing_part: &CSGMesh, input: &BirdGenInputs) -> CSGMesh {
+    let mut posed = wing_part.clone();
+    if input.wing_pose == WingPose::Spread {
+        // lay the wing flat, so the chord runs horizontally
+        posed = posed.rotate(90.0, 0.0, 0.0);
+    }
+
+    // the shoulder sits on the upper front side of the belly
+    let belly_half_width = (input.belly_size * input.belly_fat / 100.0) as f64 / 2.0;
+    posed
+        .rotate(0.0, input.wing_droop as f64, input.wing_spread as f64)
+        .translate(
+            (input.head_to_belly - input.belly_length * 0.1) as f64,
+            belly_half_width * 0.9,
+            input.belly_size as f64 * 0.15,
+        )
+}
+
+// Stripes across both wings, as slabs way bigger than the wing in every direction but the length
+fn generate_wing_bars(input: &BirdGenInputs) -> CSGMesh {
+    let length = input.wing_length as f64;
+    let width = input.wing_width as f64;
+    let bar_count = input.wing_bars.round() as usize;
+    let bar_width = length * WING_BAR_WIDTH_RATIO;
+    let mut bars = CSGMesh::new();
+    for bar in 0..bar_count {
+        // spread over the middle of the wing, clear of the shoulder and the tip
+        let bar_x = length * (0.3 + 0.5 * (bar + 1) as f64 / (bar_count + 1) as f64);
+        let slab = CSGMesh::cuboid(bar_width, width * 2.0, width * 4.0, None).translate(
+            bar_x - bar_width / 2.0,
+            -width,
+            -width * 2.0,
+        );
+        bars = bars.union(&slab);
+    }
+    let bars = place_wing(&bars, input);
+    let plane_y = Plane::from_normal([0.0, 1.0, 0.0].into(), 0.0);
+    bars.union(&bars.mirror(plane_y))
+}
+
+// Ball sunk into the front of the chest, just under the neck
+fn generate_breast_patch(input: &BirdGenInputs) -> CSGMesh {
+    let chest_front = (input.head_to_belly - input.belly_length / 2.0) as f64;
+    CSGMesh::sphere(
+        input.breast_patch as f64 / 2.0,
+        SPHERE_SEGMENTS,
+        SPHERE_STACKS,
+        None,
+    )
+    .translate(chest_front, 0.0, -input.belly_size as f64 * 0.1)
+}
+
+// Band through both eyes, running forward to the base of the beak (head coordinates)
+fn generate_face_mask(input: &BirdGenInputs) -> CSGMesh {
+    let head_size = input.head_size as f64;
+    let eye_spacing = (input.eye_spacing as f64).to_radians();
+    let eye_angle = (input.eye_angle as f64).to_radians();
+    // where `aim_eye` puts the top of the skull, seen from the side
+    let eye_x = -head_size / 2.0 * eye_spacing.cos() * eye_angle.sin();
+    let eye_z = head_size / 2.0 * eye_spacing.cos() * eye_angle.cos();
 
-        body = body.difference(&cut_box);
+    let band = CSGMesh::cylinder(
+        input.face_mask as f64 / 2.0,
+        head_size * 2.0,
+        SPHERE_SEGMENTS,
+        None,
+    )
+    .rotate(90.0, 0.0, 0.0)
+    .translate(eye_x, head_size, eye_z);
+    band.union(&band.translate(-head_size / 2.0, 0.0, 0.0))
+        .convex_hull()
+}
+
+// Round spot on the side of the head, below and a bit behind the eye (head coordinates, one side)
+fn generate_cheek_spot(input: &BirdGenInputs) -> CSGMesh {
+    let spot = CSGMesh::sphere(
+        input.cheek_spots as f64 / 2.0,
+        SPHERE_SEGMENTS / 2 + 2,
+        SPHERE_STACKS / 2 + 2,
+        None,
+    )
+    .translate(0.0, 0.0, input.head_size as f64 / 2.0);
+    let cheek_spacing = (input.eye_spacing as f64 + CHEEK_SPOT_DROP).min(150.0);
+    spot.rotate(cheek_spacing, -(input.eye_angle as f64) / 2.0, 0.0)
+}
+
+// Fan of plumes sticking out of the top of the skull (head centered on the origin, beak towards -x).
+// The middle plumes are the longest, and the fan leans back by the sweep angle. Each plume is its
+// own hull.
+fn generate_crest_plumes(input: &BirdGenInputs) -> Vec<CSGMesh> {
+    let plume_count = input.crest_plumes.round().max(1.0) as usize;
+    let plume_radius = input.crest_thickness as f64 / 2.0;
+    // sink the plume roots a bit into the skull
+    let skull_radius = input.head_size as f64 / 2.0 - plume_radius;
+
+    let mut plumes = Vec::with_capacity(plume_count);
+    for plume in 0..plume_count {
+        // -0.5 for the front plume, 0.5 for the back one
+        let fan_fraction = if plume_count > 1 {
+            plume as f64 / (plume_count - 1) as f64 - 0.5
+        } else {
+            0.0
+        };
+        let plume_angle = (input.crest_sweep as f64 + CREST_FAN_ANGLE * fan_fraction).to_radians();
+        // roots spread over the top of the skull, following the fan
+        let root_angle = plume_angle * 0.5;
+        let root = (
+            skull_radius * root_angle.sin(),
+            0.0,
+            skull_radius * root_angle.cos(),
+        );
+        let plume_length = input.crest_height as f64 * (1.0 - fan_fraction.abs() * 0.6);
+        let tip = (
+            root.0 + plume_length * plume_angle.sin(),
+            0.0,
+            root.2 + plume_length * plume_angle.cos(),
+        );
+
+        let root_blob = CSGMesh::sphere(plume_radius, SPHERE_SEGMENTS / 2, SPHERE_STACKS / 2, None)
+            .translate(root.0, root.1, root.2);
+        let tip_blob = CSGMesh::sphere(
+            plume_radius * 0.4,
+            SPHERE_SEGMENTS / 2,
+            SPHERE_STACKS / 2,
+            None,
+        )
+        .translate(tip.0, tip.1, tip.2);
+        plumes.push(root_blob.union(&tip_blob).convex_hull());
+    }
+    plumes
+}
+
+// Where the legs and perch attach, a bit behind the middle of the belly
+fn stand_x(input: &BirdGenInputs) -> f64 {
+    (input.head_to_belly + input.belly_to_bottom * 0.25) as f64
+}
+
+// Two legs hanging from the belly, with a fan of toes on each foot.
+// Also returns the height the soles get flattened at.
+fn generate_legs(input: &BirdGenInputs) -> (CSGMesh, f64) {
+    let leg_radius = input.leg_thickness as f64 / 2.0;
+    // the legs start inside the belly so the union has something to grab onto
+    let foot_z = -(input.belly_size / 2.0 + input.leg_length) as f64;
+    let toe_count = input.toe_count.round().max(1.0) as usize;
+    let toe_length = (input.leg_length as f64 * 0.5).max(leg_radius * 4.0);
+    let toe_z = foot_z + leg_radius;
+
+    let mut legs = CSGMesh::new();
+    for y in [-1.0, 1.0] {
+        let leg_y = y * input.leg_spacing as f64 / 2.0;
+        let mut leg = CSGMesh::cylinder(leg_radius, -foot_z, SPHERE_SEGMENTS, None).translate(
+            stand_x(input),
+            leg_y,
+            foot_z,
+        );
+        for toe in 0..toe_count {
+            // toes point forward (towards the head), fanned out evenly over the splay angle
+            let splay_fraction = if toe_count > 1 {
+                toe as f32 / (toe_count - 1) as f32 - 0.5
+            } else {
+                0.0
+            };
+            let toe_angle = (input.toe_splay * splay_fraction).to_radians() as f64;
+            let knuckle = CSGMesh::sphere(leg_radius, SPHERE_SEGMENTS / 2, SPHERE_STACKS / 2, None);
+            let toe_tip = CSGMesh::sphere(
+                leg_radius * 0.7,
+                SPHERE_SEGMENTS / 2,
+                SPHERE_STACKS / 2,
+                None,
+            )
+            .translate(
+                -toe_length * toe_angle.cos(),
+                toe_length * toe_angle.sin(),
+                0.0,
+            );
+            let toe = knuckle
+                .union(&toe_tip)
+                .convex_hull()
+                .translate(stand_x(input), leg_y, toe_z);
+            leg = leg.union(&toe);
+        }
+        leg.renormalize();
+        legs = legs.union(&leg);
+    }
+    // shave the bottom of the toes so the feet print flat
+    (legs, toe_z - leg_radius * 0.5)
+}
+
+// Branch running sideways under the belly, on a post sticking out of a round display base.
+// Also returns the height of the bottom of the display base.
+fn generate_perch(input: &BirdGenInputs) -> (CSGMesh, f64) {
+    let branch_radius = input.perch_diameter as f64 / 2.0;
+    // half buried in the belly, so the bird sits on it
+    let branch_z = -input.belly_size as f64 / 2.0;
+    let branch_length = (input.belly_size * input.belly_fat / 100.0) as f64 * 1.6;
+    let branch = CSGMesh::cylinder(branch_radius, branch_length, SPHERE_SEGMENTS, None)
+        .rotate(90.0, 0.0, 0.0)
+        .translate(stand_x(input), branch_length / 2.0, branch_z);
+
+    let base_top = branch_z - input.perch_height as f64;
+    let post = CSGMesh::cylinder(
+        branch_radius,
+        (branch_z - base_top).max(NONZERO_THICKNESS),
+        SPHERE_SEGMENTS,
+        None,
+    )
+    .translate(stand_x(input), 0.0, base_top);
+    let base_radius = (input.belly_length / 2.0).max(input.bottom_size) as f64;
+    let base_bottom = base_top - PERCH_BASE_THICKNESS;
+    let base = CSGMesh::cylinder(base_radius, PERCH_BASE_THICKNESS, 2 * SPHERE_SEGMENTS, None)
+        .translate(stand_x(input), 0.0, base_bottom);
+
+    let mut perch = branch.union(&post).union(&base);
+    perch.renormalize();
+    (perch, base_bottom)
+}
+
+// Name plate text laid flat (reading along +x, facing +z) and centered on the origin, `depth` thick.
+// Also returns how big the text ended up.
+fn name_text_mesh(input: &BirdGenInputs, depth: f64) -> (CSGMesh, DVec3) {
+    let text = Sketch::text(
+        input.name_text.trim(),
+        input.name_font.bytes(),
+        input.name_size as f64,
+        None,
+    )
+    .extrude(depth);
+    let bounds = MeshStats::from_meshes(&[&text]);
+    let center = (bounds.bounding_box_min + bounds.bounding_box_max) / 2.0;
+    (text.translate(-center.x, -center.y, 0.0), bounds.size())
+}
+
+// Text engraved under the base (mirrored, so it reads right with the bird flipped over),
+// or on the front of a pedestal added under the bird.
+// Also returns the new floor height (the bottom of the pedestal, if there is one).
+fn add_name_plate(
+    body: &CSGMesh,
+    floor_height: Option<f64>,
+    input: &BirdGenInputs,
+) -> (CSGMesh, Option<f64>) {
+    let depth = input.name_depth as f64;
+    match input.name_plate {
+        NamePlate::Base => {
+            // the soles are way too small to write on
+            let Some(floor) = floor_height.filter(|_| input.stand != BirdStand::Legs) else {
+                info!("No flat base to engrave, skipping name plate");
+                return (body.clone(), floor_height);
+            };
+            // embossing would stop the bird sitting flat, so this is always engraved
+            let (text, _) = name_text_mesh(input, depth + NONZERO_THICKNESS);
+            let plane_x = Plane::from_normal([1.0, 0.0, 0.0].into(), 0.0);
+            let text =
+                text.mirror(plane_x)
+                    .translate(stand_x(input), 0.0, floor - NONZERO_THICKNESS);
+            (body.difference(&text), floor_height)
+        }
+        NamePlate::Pedestal => {
+            // without a flat bottom, sink the pedestal a bit further into the round belly
+            let top = floor_height.unwrap_or(-input.belly_size as f64 * 0.4) + PEDESTAL_SINK;
+            let (text, text_size) = name_text_mesh(input, depth + NONZERO_THICKNESS);
+            let margin = input.name_size as f64 * 0.5;
+            let pedestal_height = text_size.y + 2.0 * margin + PEDESTAL_SINK;
+            let pedestal_width = (text_size.x + 2.0 * margin)
+                .max((input.belly_size * input.belly_fat / 100.0) as f64);
+            let pedestal_front = (input.head_to_belly - input.belly_length / 2.0) as f64;
+            let pedestal_length = (input.belly_length + input.belly_to_bottom) as f64;
+            let pedestal = CSGMesh::cuboid(pedestal_length, pedestal_width, pedestal_height, None)
+                .translate(pedestal_front, -pedestal_width / 2.0, top - pedestal_height);
+
+            // stand the text up on the front face (towards the head), reading left to right
+            let text_z = top - PEDESTAL_SINK - (pedestal_height - PEDESTAL_SINK) / 2.0;
+            let text = text.rotate(90.0, 0.0, -90.0);
+            let body_with_plate = match input.name_style {
+                NameStyle::Engraved => body.union(&pedestal).difference(&text.translate(
+                    pedestal_front + depth,
+                    0.0,
+                    text_z,
+                )),
+                NameStyle::Embossed => body.union(&pedestal).union(&text.translate(
+                    pedestal_front + NONZERO_THICKNESS,
+                    0.0,
+                    text_z,
+                )),
+            };
+            (body_with_plate, Some(top - pedestal_height))
+        }
+        NamePlate::Off => (body.clone(), floor_height),
+    }
+}
+
+// Hanging loop on the back, plus a magnet pocket and a countersunk screw hole in the flat bottom.
+// The bottom ones need a flat floor to go into, so they get skipped for legs or an uncut belly.
+fn add_mounting(body: &CSGMesh, floor_height: Option<f64>, input: &BirdGenInputs) -> CSGMesh {
+    let mut body = body.clone();
+
+    if input.loop_diameter > 0.0 {
+        info!("Adding hanging loop");
+        body = body.union(&generate_hanging_loop(&body, input));
         body.renormalize();
     }
 
-    info!("Make bevy mesh");
+    let flat_floor = floor_height.filter(|_| input.stand != BirdStand::Legs);
+    if flat_floor.is_none() && (input.magnet_size != MagnetSize::Off || input.screw_diameter > 0.0)
+    {
+        info!("No flat base for the magnet/screw, skipping them");
+    }
+    let Some(floor) = flat_floor else {
+        return body;
+    };
 
-    // add the x axis rotation to account for y up world we're rocking with in bevy
-    body.rotate(-90.0, 180.0, 0.0).to_bevy_mesh()
+    if let Some((magnet_diameter, magnet_thickness)) = input.magnet_size.dimensions() {
+        info!("Cutting magnet pocket");
+        // opens out of the bottom, poking through a little so the cut is clean
+        let pocket_height = magnet_thickness + MAGNET_CLEARANCE + NONZERO_THICKNESS;
+        let pocket = CSGMesh::cylinder(
+            magnet_diameter / 2.0 + MAGNET_CLEARANCE,
+            pocket_height,
+            SPHERE_SEGMENTS,
+            None,
+        )
+        .translate(stand_x(input), 0.0, floor - NONZERO_THICKNESS);
+        body = body.difference(&pocket);
+        body.renormalize();
+    }
+
+    if input.screw_diameter > 0.0 {
+        info!("Cutting screw hole");
+        // shuffled back towards the tail when there's a magnet in the way
+        let screw_x = stand_x(input)
+            + input
+                .magnet_size
+                .dimensions()
+                .map_or(0.0, |(magnet_diameter, _)| {
+                    magnet_diameter / 2.0
+                        + MAGNET_CLEARANCE
+                        + SCREW_MAGNET_GAP
+                        + input.screw_diameter as f64 * SCREW_HEAD_RATIO / 2.0
+                        + SCREW_CLEARANCE
+                });
+        body = body.difference(&generate_screw_hole(floor, screw_x, input));
+        body.renormalize();
+    }
+    body
+}
+
+// Cavity for the container mode: the hull of the belly shrunk by the wall thickness
+// (optionally narrowed to a cylinder), kept above the floor, with a round opening going
+// straight up out of the back. None if the walls don't leave any room.
+fn generate_container_cavity(
+    container_hull: &CSGMesh,
+    floor_height: Option<f64>,
+    input: &BirdGenInputs,
+) -> Option<CSGMesh> {
+    let wall = input.container_wall as f64;
+    let inside = inset_convex(container_hull, wall)?;
+    let bounds = MeshStats::from_meshes(&[&inside]);
+    let center = (bounds.bounding_box_min + bounds.bounding_box_max) / 2.0;
+
+    let opening_radius = input.container_opening as f64 / 2.0;
+    // way taller than any bird, so it comes out the top
+    let reach = (input.belly_size + input.head_level + input.head_size) as f64 * 2.0;
+    let column = |from_z: f64| {
+        CSGMesh::cylinder(opening_radius, reach, SPHERE_SEGMENTS, None)
+            .translate(center.x, 0.0, from_z)
+    };
+    let mut cavity = match input.container {
+        ContainerShape::Cylinder => inside.intersection(&column(center.z - reach / 2.0)),
+        _ => inside,
+    }
+    .union(&column(center.z));
+
+    // a solid bottom, as thick as the walls
+    if let Some(floor) = floor_height {
+        cavity = cut_below(&cavity, floor + wall, input);
+    }
+    if input.drainage_diameter > 0.0 {
+        let drain = CSGMesh::cylinder(
+            input.drainage_diameter as f64 / 2.0,
+            reach,
+            SPHERE_SEGMENTS,
+            None,
+        )
+        .translate(center.x, 0.0, center.z - reach);
+        cavity = cavity.union(&drain);
+    }
+    cavity.renormalize();
+    Some(cavity)
+}
+
+// Whistle carved into the body: a resonant chamber in the belly, a window going up out of the
+// back over it with a sharp labium edge, and a thin windway running back to a mouthpiece tube
+// sticking out past the tail. Blowing in the mouthpiece splits the air on the labium,
+// and the chamber behind it sets the note (roughly like a Helmholtz resonator).
+// Returns the mouthpiece (to add to the body) and all the air (to take out of it afterwards).
+fn generate_whistle(
+    body: &CSGMesh,
+    whistle_hull: &CSGMesh,
+    floor_height: Option<f64>,
+    input: &BirdGenInputs,
+) -> Option<(CSGMesh, CSGMesh)> {
+    // chamber is the inset belly, shrunk around its middle down to the wanted share of the volume
+    let inside = inset_convex(whistle_hull, WHISTLE_WALL)?;
+    let inside_bounds = MeshStats::from_meshes(&[&inside]);
+    let center = (inside_bounds.bounding_box_min + inside_bounds.bounding_box_max) / 2.0;
+    let chamber_scale = (input.whistle_chamber as f64 / 100.0).cbrt();
+    let mut chamber = inside
+        .translate(-center.x, -center.y, -center.z)
+        .scale(chamber_scale, chamber_scale, chamber_scale)
+        .translate(center.x, center.y, center.z);
+    if let Some(floor) = floor_height {
+        chamber = cut_below(&chamber, floor + WHISTLE_WALL, input);
+    }
+    let chamber_bounds = MeshStats::from_meshes(&[&chamber]);
+    if chamber_bounds.triangle_count == 0 {
+        return None;
+    }
+    let body_bounds = MeshStats::from_meshes(&[body]);
+
+    // everything happens in a slice across the middle, the windway width wide
+    let half_width = input.windway_width as f64 / 2.0;
+    let slot = |outline: &[(f64, f64)]| {
+        let corners: Vec<DVec3> = outline
+            .iter()
+            .flat_map(|(x, z)| {
+                [
+                    DVec3::new(*x, -half_width, *z),
+                    DVec3::new(*x, half_width, *z),
+                ]
+            })
+            .collect();
+        point_cloud(&corners).convex_hull()
+    };
+
+    // the jet comes out of the windway just over the top of the chamber, heading for the head
+    let jet_height = chamber_bounds.bounding_box_max.z + WINDWAY_FLOOR + WINDWAY_HEIGHT / 2.0;
+    let window_length = WINDWAY_HEIGHT * WINDOW_LENGTH_RATIO;
+    let window_back = center.x + window_length / 2.0;
+    let edge_x = window_back - window_length;
+    let windway_bottom = jet_height - WINDWAY_HEIGHT / 2.0;
+    let labium_front = edge_x - LABIUM_LENGTH;
+    let top_angle = LABIUM_TOP_ANGLE.to_radians();
+    let bottom_angle = LABIUM_BOTTOM_ANGLE.to_radians();
+
+    // window out of the back, the labium's top face leaning away from the edge
+    let open_air = body_bounds.bounding_box_max.z + 1.0;
+    let window = slot(&[
+        (window_back, windway_bottom),
+        (edge_x, jet_height),
+        (labium_front, jet_height + LABIUM_LENGTH * top_angle.tan()),
+        (labium_front, open_air),
+        (window_back, open_air),
+    ]);
+    // throat under the labium down into the chamber
+    let throat = slot(&[
+        (window_back, windway_bottom),
+        (edge_x, jet_height),
+        (
+            labium_front,
+            jet_height - LABIUM_LENGTH * bottom_angle.tan(),
+        ),
+        (labium_front, center.z),
+        (window_back, center.z),
+    ]);
+
+    // windway from the window back out through the tail, with a tube around it so there's always
+    // something to put your lips on (and enough wall around the slot)
+    let mouthpiece_end = body_bounds.bounding_box_max.x + 2.0;
+    let windway_length = mouthpiece_end - window_back;
+    let windway = CSGMesh::cuboid(windway_length + 1.0, half_width * 2.0, WINDWAY_HEIGHT, None)
+        .translate(window_back, -half_width, windway_bottom);
+    let mouthpiece = CSGMesh::cylinder(
+        half_width + WHISTLE_WALL,
+        windway_length,
+        SPHERE_SEGMENTS,
+        None,
+    )
+    .rotate(0.0, 90.0, 0.0)
+    .translate(window_back, 0.0, jet_height);
+
+    // rough note: a thin walled opening the size of the window, over the chamber volume
+    let opening_area = window_length * half_width * 2.0;
+    let neck_length = 1.7 * (opening_area / std::f64::consts::PI).sqrt();
+    let pitch = SPEED_OF_SOUND / (2.0 * std::f64::consts::PI)
+        * (opening_area / (chamber_bounds.volume * neck_length)).sqrt();
+    info!(
+        "Whistle chamber {:.0}mm^3, should sound somewhere around {:.0}Hz",
+        chamber_bounds.volume, pitch
+    );
+
+    let mut air_path = chamber.union(&throat).union(&window).union(&windway);
+    air_path.renormalize();
+    Some((mouthpiece, air_path))
+}
+
+// Shrink a convex mesh by `distance` all around, by moving every face plane in.
+// Done through the polar dual (around the middle of the mesh): each plane turns into a point,
+// and each face of the hull of those points turns back into a corner of the shrunk mesh.
+// None when the mesh is too thin to shrink that much.
+fn inset_convex(mesh: &CSGMesh, distance: f64) -> Option<CSGMesh> {
+    let corners: Vec<DVec3> = mesh
+        .polygons
+        .iter()
+        .flat_map(|polygon| polygon.vertices.iter())
+        .map(|vertex| DVec3::new(vertex.pos.x, vertex.pos.y, vertex.pos.z))
+        .collect();
+    if corners.is_empty() {
+        return None;
+    }
+    let middle = corners.iter().sum::<DVec3>() / corners.len() as f64;
+
+    // the plane of every face (relative to `origin`), as normal and distance
+    let face_planes = |mesh: &CSGMesh, origin: DVec3| -> Vec<(DVec3, f64)> {
+        mesh.polygons
+            .iter()
+            .filter_map(|polygon| {
+                let points: Vec<DVec3> = polygon
+                    .vertices
+                    .iter()
+                    .map(|vertex| DVec3::new(vertex.pos.x, vertex.pos.y, vertex.pos.z) - origin)
+                    .collect();
+                let normal = (points.get(1)? - points[0])
+                    .cross(points.get(2)? - points[0])
+                    .try_normalize()?;
+                Some((normal, normal.dot(points[0])))
+            })
+            .collect()
+    };
+
+    let mut dual_points = Vec::new();
+    for (normal, plane_distance) in face_planes(mesh, middle) {
+        // make sure the normal points out, then move the plane in
+        let (normal, plane_distance) = if plane_distance < 0.0 {
+            (-normal, -plane_distance)
+        } else {
+            (normal, plane_distance)
+        };
+        let shrunk_distance = plane_distance - distance;
+        if shrunk_distance <= 0.0 {
+            return None;
+        }
+        dual_points.push(normal / shrunk_distance);
+    }
+
+    let inset_corners: Vec<DVec3> =
+        face_planes(&point_cloud(&dual_points).convex_hull(), DVec3::ZERO)
+            .into_iter()
+            // flipping the normal flips the distance too, so the corner comes out the same either way
+            .filter(|(_, plane_distance)| plane_distance.abs() > f64::EPSILON)
+            .map(|(normal, plane_distance)| middle + normal / plane_distance)
+            .collect();
+    let mut inset = point_cloud(&inset_corners).convex_hull();
+    inset.renormalize();
+    Some(inset)
+}
+
+// Bunch of points as a (nonsense) mesh, just so convex_hull can wrap them up
+fn point_cloud(points: &[DVec3]) -> CSGMesh {
+    let vertex =
+        |point: &DVec3| Vertex::new([point.x, point.y, point.z].into(), [0.0, 0.0, 1.0].into());
+    let polygons: Vec<Polygon<()>> = (0..points.len())
+        .step_by(3)
+        .map(|i| {
+            Polygon::new(
+                (i..i + 3)
+                    .map(|j| vertex(&points[j % points.len()]))
+                    .collect(),
+                None,
+            )
+        })
+        .collect();
+    CSGMesh::from_polygons(&polygons, None)
+}
+
+// Ring standing up along the back, half its wall sunk into the highest point of the back around
+// `loop_position`, so the hole itself stays clear of the body
+fn generate_hanging_loop(body: &CSGMesh, input: &BirdGenInputs) -> CSGMesh {
+    let hole_radius = input.loop_diameter as f64 / 2.0;
+    let wall = (hole_radius * 0.5).max(2.0);
+    let back_length = (input.head_to_belly + input.belly_to_bottom) as f64;
+    let loop_x = back_length * input.loop_position as f64 / 100.0;
+
+    // highest body vertex around there, on the centerline
+    let search_window = input.belly_size as f64 / 4.0;
+    let back_top = body
+        .polygons
+        .iter()
+        .flat_map(|polygon| polygon.vertices.iter())
+        .filter(|vertex| {
+            (vertex.pos.x - loop_x).abs() < search_window && vertex.pos.y.abs() < search_window
+        })
+        .map(|vertex| vertex.pos.z)
+        .reduce(f64::max)
+        .unwrap_or(input.belly_size as f64 / 2.0);
+
+    // cylinders stand along z, lay them along y so the string goes through sideways
+    let ring = |radius: f64, thickness: f64| {
+        CSGMesh::cylinder(radius, thickness, SPHERE_SEGMENTS, None)
+            .rotate(90.0, 0.0, 0.0)
+            .translate(loop_x, thickness / 2.0, back_top + hole_radius + wall / 2.0)
+    };
+    ring(hole_radius + wall, wall).difference(&ring(hole_radius, wall * 2.0))
+}
+
+// Screw going up into the bird from the flat bottom at `x`, countersunk `screw_depth` above the
+// floor, with a blind pocket above the head. The screw drops in during a pause in the print, so
+// the back of the bird stays closed.
+fn generate_screw_hole(floor: f64, x: f64, input: &BirdGenInputs) -> CSGMesh {
+    let shank_radius = input.screw_diameter as f64 / 2.0 + SCREW_CLEARANCE;
+    let head_radius = input.screw_diameter as f64 * SCREW_HEAD_RATIO / 2.0 + SCREW_CLEARANCE;
+    let seat = floor + input.screw_depth as f64;
+    let shank = CSGMesh::cylinder(
+        shank_radius,
+        seat - floor + NONZERO_THICKNESS * 2.0,
+        SPHERE_SEGMENTS,
+        None,
+    )
+    .translate(0.0, 0.0, floor - NONZERO_THICKNESS);
+    // 90 degree countersink, as wide as the head
+    let countersink_height = head_radius - shank_radius;
+    let countersink = CSGMesh::frustum(
+        shank_radius,
+        head_radius,
+        countersink_height,
+        SPHERE_SEGMENTS,
+        None,
+    )
+    .translate(0.0, 0.0, seat);
+    let head_room = CSGMesh::cylinder(
+        head_radius,
+        SCREW_HEAD_ROOM + NONZERO_THICKNESS,
+        SPHERE_SEGMENTS,
+        None,
+    )
+    .translate(0.0, 0.0, seat + countersink_height - NONZERO_THICKNESS);
+    shank
+        .union(&countersink)
+        .union(&head_room)
+        .translate(x, 0.0, 0.0)
+}
+
+// Remove everything under the given height, e.g. for flat feet
+fn cut_below(mesh: &CSGMesh, height: f64, input: &BirdGenInputs) -> CSGMesh {
+    let total_len =
+        (input.beak_length + input.head_to_belly + input.belly_to_bottom + input.tail_length)
+            as f64;
+    let box_size = total_len * 10.0;
+    let cut_box = CSGMesh::cuboid(box_size, box_size, box_size, None).translate(
+        -box_size / 2.0,
+        -box_size / 2.0,
+        height - box_size,
+    );
+    mesh.difference(&cut_box)
+}
+
+// Matching peg (unioned onto the head) and socket (cut from the neck) for printing the head on its own.
+// Both are vertical cylinders hanging down from the center of the placed head,
+// so the peg pokes `peg_length` out of the bottom of the skull.
+fn split_head_joint(input: &BirdGenInputs) -> (CSGMesh, CSGMesh) {
+    let head_radius = HEAD_SCALE * input.head_size as f64 / 2.0;
+    let center_y = HEAD_SCALE * input.head_lateral_offset as f64;
+    let center_z = HEAD_SCALE * input.head_level as f64;
+    // keep the peg well inside the skull, even for tiny heads
+    let peg_radius = (input.peg_diameter as f64 / 2.0).min(head_radius * 0.6);
+    let clearance = input.peg_clearance as f64;
+
+    let peg_height = head_radius + input.peg_length as f64;
+    let peg = CSGMesh::cylinder(peg_radius, peg_height, SPHERE_SEGMENTS, None).translate(
+        0.0,
+        center_y,
+        center_z - peg_height,
+    );
+
+    let socket_height = peg_height + clearance;
+    let socket = CSGMesh::cylinder(peg_radius + clearance, socket_height, SPHERE_SEGMENTS, None)
+        .translate(0.0, center_y, center_z - socket_height);
+    (peg, socket)
 }
 /* From https://www.thingiverse.com/thing:139945/files
 
@@ -0,0 +1,341 @@
+use std::io::{Cursor, Write};
+
+use bevy::{color::ColorToPacked, math::DVec3};
+use csgrs::traits::CSG;
+use serde_json::json;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    bird::{BirdGenInputs, BirdPart, BirdParts, CSGMesh, part_colour, shading_angle},
+    remesh::IndexedMesh,
+    stats::{MeshStats, mesh_triangles},
+};
+
+// Gap between the body and the head, when the head gets printed on its own
+const PIECE_GAP: f64 = 10.0;
+
+// One piece that gets printed, with the parts that make it up
+struct PrintPiece {
+    name: &'static str,
+    parts: Vec<(BirdPart, CSGMesh)>,
+}
+
+// The bird laid out on the build plate the way it gets printed. Normally that's all in one piece,
+// but with `split_head` the head (and everything on it) comes off as a piece of its own, turned
+// over so the peg points up and set down next to the body.
+fn print_pieces(parts: &BirdParts, input: &BirdGenInputs) -> Vec<PrintPiece> {
+    let on_bed = |pieces: Vec<(BirdPart, CSGMesh)>| {
+        let meshes: Vec<&CSGMesh> = pieces.iter().map(|(_, mesh)| mesh).collect();
+        let floor = MeshStats::from_meshes(&meshes).bounding_box_min.z;
+        pieces
+            .into_iter()
+            .map(|(part, mesh)| (part, mesh.translate(0.0, 0.0, -floor)))
+            .collect::<Vec<_>>()
+    };
+    let split = input.split_head && parts.iter().any(|(part, _)| part == BirdPart::Head);
+    let (head, body): (Vec<_>, Vec<_>) = parts
+        .iter()
+        .map(|(part, mesh)| (part, mesh.clone()))
+        .partition(|(part, _)| split && part.on_head());
+    let body = on_bed(body);
+    if head.is_empty() {
+        return vec![PrintPiece {
+            name: "Bird",
+            parts: body,
+        }];
+    }
+
+    let head = on_bed(
+        head.into_iter()
+            .map(|(part, mesh)| (part, mesh.rotate(180.0, 0.0, 0.0)))
+            .collect(),
+    );
+    let body_meshes: Vec<&CSGMesh> = body.iter().map(|(_, mesh)| mesh).collect();
+    let head_meshes: Vec<&CSGMesh> = head.iter().map(|(_, mesh)| mesh).collect();
+    let shift = MeshStats::from_meshes(&body_meshes).bounding_box_max.y + PIECE_GAP
+        - MeshStats::from_meshes(&head_meshes).bounding_box_min.y;
+    let head = head
+        .into_iter()
+        .map(|(part, mesh)| (part, mesh.translate(0.0, shift, 0.0)))
+        .collect();
+    vec![
+        PrintPiece {
+            name: "Body",
+            parts: body,
+        },
+        PrintPiece {
+            name: "Head",
+            parts: head,
+        },
+    ]
+}
+
+// 3MF with one object per part, each with its own colour, grouped into an object per printed
+// piece (so a multi material slicer can assign a filament per part). Laid out like `print_pieces`.
+pub fn bird_3mf(parts: &BirdParts, input: &BirdGenInputs) -> Vec<u8> {
+    let pieces = print_pieces(parts, input);
+
+    let mut materials = String::new();
+    let mut objects = String::new();
+    let mut groups = String::new();
+    let mut items = String::new();
+    // object ids start at 2, the materials are resource 1
+    let mut next_id = 2;
+    let mut material_index = 0;
+    for piece in pieces.iter() {
+        let mut components = String::new();
+        for (part, mesh) in piece.parts.iter() {
+            let [r, g, b, a] = part_colour(*part, input)
+                .to_color()
+                .to_srgba()
+                .to_u8_array();
+            materials += &format!(
+                "      <base name=\"{}\" displaycolor=\"#{:02X}{:02X}{:02X}{:02X}\" />\n",
+                part.name(),
+                r,
+                g,
+                b,
+                a
+            );
+
+            let object_id = next_id;
+            next_id += 1;
+            let indexed = IndexedMesh::from_csg(mesh);
+            objects += &format!(
+                "    <object id=\"{}\" name=\"{}\" type=\"model\" pid=\"1\" pindex=\"{}\">\n      <mesh>\n        <vertices>\n",
+                object_id,
+                part.name(),
+                material_index
+            );
+            material_index += 1;
+            for vertex in indexed.vertices.iter() {
+                objects += &format!(
+                    "          <vertex x=\"{:.4}\" y=\"{:.4}\" z=\"{:.4}\" />\n",
+                    vertex.x, vertex.y, vertex.z
+                );
+            }
+            objects += "        </vertices>\n        <triangles>\n";
+            for [v1, v2, v3] in indexed.triangles.iter() {
+                objects += &format!(
+                    "          <triangle v1=\"{}\" v2=\"{}\" v3=\"{}\" />\n",
+                    v1, v2, v3
+                );
+            }
+            objects += "        </triangles>\n      </mesh>\n    </object>\n";
+            components += &format!("        <component objectid=\"{}\" />\n", object_id);
+        }
+        groups += &format!(
+            "    <object id=\"{}\" name=\"{}\" type=\"model\">\n      <components>\n{}      </components>\n    </object>\n",
+            next_id, piece.name, components
+        );
+        items += &format!("    <item objectid=\"{}\" />\n", next_id);
+        next_id += 1;
+    }
+
+    let model = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xml:lang="en-US" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <metadata name="Title">rusty-bird</metadata>
+  <metadata name="Application">rusty-bird</metadata>
+  <resources>
+    <basematerials id="1">
+{materials}    </basematerials>
+{objects}{groups}  </resources>
+  <build>
+{items}  </build>
+</model>
+"#
+    );
+
+    write_zip(&[
+        ("[Content_Types].xml", CONTENT_TYPES_XML),
+        ("_rels/.rels", RELS_XML),
+        ("3D/3dmodel.model", &model),
+    ])
+}
+
+const CONTENT_TYPES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml" />
+  <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml" />
+</Types>
+"#;
+
+const RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+</Relationships>
+"#;
+
+fn write_zip(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, contents) in files {
+        // writing into a Vec can't really fail
+        zip.start_file(*name, options).unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+// Binary STL files of the bird, one per piece from `print_pieces` (with the file name to save it as).
+// STL only has one normal per triangle, so it always comes out flat shaded (and single colour).
+pub fn bird_stls(parts: &BirdParts, input: &BirdGenInputs) -> Vec<(String, Vec<u8>)> {
+    let pieces = print_pieces(parts, input);
+    let single = pieces.len() == 1;
+    pieces
+        .iter()
+        .map(|piece| {
+            let file_name = if single {
+                "bird.stl".to_string()
+            } else {
+                format!("bird-{}.stl", piece.name.to_lowercase())
+            };
+            let meshes: Vec<&CSGMesh> = piece.parts.iter().map(|(_, mesh)| mesh).collect();
+            (file_name, meshes_stl(&meshes))
+        })
+        .collect()
+}
+
+// Binary STL of any meshes, lifted onto the build plate
+pub fn meshes_stl(meshes: &[&CSGMesh]) -> Vec<u8> {
+    let stats = MeshStats::from_meshes(meshes);
+    let lift = DVec3::new(0.0, 0.0, -stats.bounding_box_min.z);
+    let triangles: Vec<[DVec3; 3]> = meshes
+        .iter()
+        .flat_map(|mesh| mesh_triangles(mesh))
+        .collect();
+
+    let mut stl = Vec::with_capacity(84 + triangles.len() * 50);
+    let mut header = [0u8; 80];
+    header[..10].copy_from_slice(b"rusty-bird");
+    stl.extend_from_slice(&header);
+    stl.extend_from_slice(&(triangles.len() as u32).to_le_bytes());
+    for [a, b, c] in triangles {
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        for value in [normal, a + lift, b + lift, c + lift] {
+            for component in value.to_array() {
+                stl.extend_from_slice(&(component as f32).to_le_bytes());
+            }
+        }
+        // attribute byte count, unused
+        stl.extend_from_slice(&0u16.to_le_bytes());
+    }
+    stl
+}
+
+const GLB_MAGIC: u32 = 0x4654_6C67; // "glTF"
+const GLB_CHUNK_JSON: u32 = 0x4E4F_534A;
+const GLB_CHUNK_BIN: u32 = 0x004E_4942;
+const GLTF_FLOAT: u32 = 5126;
+
+// Binary glTF with a node (and material) per part, y up and in meters like glTF wants.
+// Every triangle gets its own vertices, with the normals smoothed like on screen.
+pub fn bird_glb(parts: &BirdParts, input: &BirdGenInputs) -> Vec<u8> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut meshes = Vec::new();
+    let mut materials = Vec::new();
+    let mut nodes = Vec::new();
+
+    for (index, (part, mesh)) in parts.iter().enumerate() {
+        // same rotation as the bevy meshes, so the bird faces the same way
+        let indexed = IndexedMesh::from_csg(&mesh.rotate(-90.0, 180.0, 0.0));
+        let corner_normals = indexed.corner_normals(shading_angle(input));
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        for (triangle, corner_normals) in indexed.triangles.iter().zip(corner_normals) {
+            for (vertex, normal) in triangle.iter().zip(corner_normals) {
+                positions.push(indexed.vertices[*vertex] / 1000.0);
+                normals.push(normal);
+            }
+        }
+        let (min, max) = positions.iter().fold(
+            (DVec3::splat(f64::MAX), DVec3::splat(f64::MIN)),
+            |(min, max), position| (min.min(*position), max.max(*position)),
+        );
+
+        let position_accessor = accessors.len();
+        for (data, bounds) in [(&positions, Some((min, max))), (&normals, None)] {
+            let byte_offset = buffer.len();
+            for value in data.iter() {
+                for component in value.to_array() {
+                    buffer.extend_from_slice(&(component as f32).to_le_bytes());
+                }
+            }
+            buffer_views.push(json!({
+                "buffer": 0,
+                "byteOffset": byte_offset,
+                "byteLength": buffer.len() - byte_offset,
+                "target": 34962, // ARRAY_BUFFER
+            }));
+            let mut accessor = json!({
+                "bufferView": buffer_views.len() - 1,
+                "componentType": GLTF_FLOAT,
+                "count": data.len(),
+                "type": "VEC3",
+            });
+            // positions need their bounds
+            if let Some((min, max)) = bounds {
+                accessor["min"] = json!(min.as_vec3().to_array());
+                accessor["max"] = json!(max.as_vec3().to_array());
+            }
+            accessors.push(accessor);
+        }
+
+        let colour = part_colour(part, input).to_color().to_linear();
+        materials.push(json!({
+            "name": part.name(),
+            "pbrMetallicRoughness": {
+                "baseColorFactor": [colour.red, colour.green, colour.blue, 1.0],
+                "metallicFactor": 0.0,
+                "roughnessFactor": 0.8,
+            },
+        }));
+        meshes.push(json!({
+            "name": part.name(),
+            "primitives": [{
+                "attributes": {
+                    "POSITION": position_accessor,
+                    "NORMAL": position_accessor + 1,
+                },
+                "material": index,
+            }],
+        }));
+        nodes.push(json!({ "name": part.name(), "mesh": index }));
+    }
+
+    let gltf = json!({
+        "asset": { "version": "2.0", "generator": "rusty-bird" },
+        "scene": 0,
+        "scenes": [{ "name": "Bird", "nodes": (0..nodes.len()).collect::<Vec<_>>() }],
+        "nodes": nodes,
+        "meshes": meshes,
+        "materials": materials,
+        "accessors": accessors,
+        "bufferViews": buffer_views,
+        "buffers": [{ "byteLength": buffer.len() }],
+    });
+
+    // chunks have to be 4 byte aligned, json gets padded with spaces and the binary with zeros
+    let mut json_chunk = gltf.to_string().into_bytes();
+    while !json_chunk.len().is_multiple_of(4) {
+        json_chunk.push(b' ');
+    }
+    while !buffer.len().is_multiple_of(4) {
+        buffer.push(0);
+    }
+
+    let total_length = 12 + 8 + json_chunk.len() + 8 + buffer.len();
+    let mut glb = Vec::with_capacity(total_length);
+    glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+    glb.extend_from_slice(&2u32.to_le_bytes());
+    glb.extend_from_slice(&(total_length as u32).to_le_bytes());
+    for (chunk_type, chunk) in [(GLB_CHUNK_JSON, &json_chunk), (GLB_CHUNK_BIN, &buffer)] {
+        glb.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&chunk_type.to_le_bytes());
+        glb.extend_from_slice(chunk);
+    }
+    glb
+}
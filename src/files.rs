@@ -0,0 +1,89 @@
+use bevy::log::{info, warn};
+
+use crate::bird::BirdGenInputs;
+
+const DESIGN_FILE_NAME: &str = "bird-design.json";
+
+// Save a file for the user: next to the app when running natively, as a download in the browser
+pub fn save_file(file_name: &str, bytes: &[u8]) {
+    #[cfg(not(target_arch = "wasm32"))]
+    match std::fs::write(file_name, bytes) {
+        Ok(()) => info!("Saved {}", file_name),
+        Err(err) => warn!("Couldn't save {}: {}", file_name, err),
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    if let Err(err) = web::download(file_name, bytes) {
+        warn!("Couldn't download {}: {:?}", file_name, err);
+    }
+}
+
+pub fn save_design(inputs: &BirdGenInputs) {
+    let Ok(design) = serde_json::to_string_pretty(inputs) else {
+        warn!("Couldn't turn the design into JSON");
+        return;
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    save_file(DESIGN_FILE_NAME, design.as_bytes());
+
+    // in the browser the design goes into local storage, a download would be awkward to load back
+    #[cfg(target_arch = "wasm32")]
+    match web::local_storage().map(|storage| storage.set_item(DESIGN_FILE_NAME, &design)) {
+        Some(Ok(())) => info!("Saved design"),
+        _ => warn!("Couldn't save design to local storage"),
+    }
+}
+
+// Last saved design, if there is one (and it still makes sense)
+pub fn load_design() -> Option<BirdGenInputs> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let design = std::fs::read_to_string(DESIGN_FILE_NAME).ok()?;
+
+    #[cfg(target_arch = "wasm32")]
+    let design = web::local_storage()?
+        .get_item(DESIGN_FILE_NAME)
+        .ok()
+        .flatten()?;
+
+    match serde_json::from_str(&design) {
+        Ok(inputs) => Some(inputs),
+        Err(err) => {
+            warn!("Couldn't read saved design: {}", err);
+            None
+        }
+    }
+}
+
+// A design saved anywhere, for the command line
+pub fn read_design(path: &str) -> Result<BirdGenInputs, String> {
+    let design =
+        std::fs::read_to_string(path).map_err(|err| format!("Couldn't read {}: {}", path, err))?;
+    serde_json::from_str(&design).map_err(|err| format!("Couldn't read design {}: {}", path, err))
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::{Blob, HtmlAnchorElement, Storage, Url};
+
+    pub fn local_storage() -> Option<Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    // Click a temporary link pointing at a blob of the bytes
+    pub fn download(file_name: &str, bytes: &[u8]) -> Result<(), JsValue> {
+        let window = web_sys::window().ok_or("no window")?;
+        let document = window.document().ok_or("no document")?;
+
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+        let blob = Blob::new_with_u8_array_sequence(&parts)?;
+        let url = Url::create_object_url_with_blob(&blob)?;
+
+        let link: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+        link.set_href(&url);
+        link.set_download(file_name);
+        link.click();
+        Url::revoke_object_url(&url)
+    }
+}
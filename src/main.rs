@@ -1,5 +1,9 @@
 use crate::{
-    bird::{BirdGenInputs, generate_bird_body_mesh, generate_bird_head_mesh},
+    bird::{
+        BirdGenInputs, BirdPart, BirdParts, bird_csg_to_bevy_mesh, generate_bird_parts, part_colour,
+    },
+    files::read_design,
+    stats::{BirdStats, MeshStats, PrintEstimate, bird_stats_json},
     ui::BirdUIPlugin,
 };
 use bevy::{
@@ -14,7 +18,18 @@ use bevy::{
 };
 
 mod bird;
+mod export;
+mod files;
+mod mould;
+mod outline;
+mod papercraft;
+mod relief;
+mod remesh;
+mod sdf;
+mod slices;
+mod stats;
 mod ui;
+mod voxel;
 
 const BG_COLOR: Color = Color::srgb(0.47, 0.49, 0.68);
 
@@ -28,6 +43,24 @@ enum BirdState {
 }
 
 fn main() {
+    // `rusty-bird --stats [bird-design.json]` prints the stats of a saved design (or the default
+    // bird) as JSON instead of opening a window
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(stats_arg) = args.iter().position(|arg| arg == "--stats") {
+        let inputs = match args.get(stats_arg + 1) {
+            Some(path) => match read_design(path) {
+                Ok(inputs) => inputs,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            },
+            None => BirdGenInputs::default(),
+        };
+        print_bird_stats(&inputs);
+        return;
+    }
+
     App::new()
         .add_plugins(
             DefaultPlugins.set(WindowPlugin {
@@ -46,6 +79,7 @@ fn main() {
         .insert_state(BirdState::BirdVisible)
         .insert_resource(ClearColor(BG_COLOR))
         .insert_resource(BirdGenInputs::default())
+        .insert_resource(BirdStats::default())
         .add_plugins(BirdUIPlugin)
         .add_systems(Startup, (spawn_camera_and_light, kick_off_bird_load))
         .add_systems(
@@ -55,6 +89,7 @@ fn main() {
                 touch_system,
                 mouse_drag_system,
                 zoom_system,
+                update_bird_colours,
             ),
         )
         .add_systems(OnEnter(BirdState::Loading), spawn_bird_mesh)
@@ -82,34 +117,66 @@ fn handle_bird_rebuild(
 #[derive(Component)]
 struct BirdMesh;
 
+// Which part of the bird a mesh is, so its colour can follow the inputs
+#[derive(Component)]
+struct BirdPartMesh(BirdPart);
+
+// The csgrs parts of the bird on screen, kept around for exporting
+#[derive(Resource)]
+struct CurrentBird(BirdParts);
+
 fn spawn_bird_mesh(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut next_bird_state: ResMut<NextState<BirdState>>,
+    mut bird_stats: ResMut<BirdStats>,
     bird_inputs: Res<BirdGenInputs>,
 ) {
     info!("time to spawn bird");
-    let basic_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.83, 0.26, 0.17),
-        ..default()
-    });
     let current_bird_inputs = bird_inputs.into_inner();
-    commands.spawn((
-        Mesh3d(meshes.add(generate_bird_head_mesh(current_bird_inputs))),
-        MeshMaterial3d(basic_material.clone()),
-        Transform::from_xyz(0.0, 0.0, 0.0),
-        BirdMesh,
-    ));
-    commands.spawn((
-        Mesh3d(meshes.add(generate_bird_body_mesh(current_bird_inputs))),
-        MeshMaterial3d(basic_material),
-        Transform::from_xyz(0.0, 0.0, 0.0),
-        BirdMesh,
-    ));
+    let bird_parts = generate_bird_parts(current_bird_inputs);
+    bird_stats.mesh = MeshStats::from_parts(&bird_parts);
+    for (part, mesh) in bird_parts.iter() {
+        let part_material = materials.add(StandardMaterial {
+            base_color: part_colour(part, current_bird_inputs).to_color(),
+            ..default()
+        });
+        commands.spawn((
+            Mesh3d(meshes.add(bird_csg_to_bevy_mesh(mesh, current_bird_inputs))),
+            MeshMaterial3d(part_material),
+            Transform::from_xyz(0.0, 0.0, 0.0),
+            BirdMesh,
+            BirdPartMesh(part),
+        ));
+    }
+    commands.insert_resource(CurrentBird(bird_parts));
     next_bird_state.set(BirdState::BirdVisible);
 }
 
+// Colours don't need a rebuild, just poke the materials
+fn update_bird_colours(
+    bird_inputs: Res<BirdGenInputs>,
+    part_meshes: Query<(&BirdPartMesh, &MeshMaterial3d<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !bird_inputs.is_changed() {
+        return;
+    }
+    for (part_mesh, material) in part_meshes.iter() {
+        if let Some(material) = materials.get_mut(material) {
+            material.base_color = part_colour(part_mesh.0, &bird_inputs).to_color();
+        }
+    }
+}
+
+fn print_bird_stats(bird_inputs: &BirdGenInputs) {
+    let bird_parts = generate_bird_parts(bird_inputs);
+    let mesh_stats = MeshStats::from_parts(&bird_parts);
+    let estimate = PrintEstimate::from_mesh_stats(&mesh_stats, bird_inputs);
+    println!("{}", bird_stats_json(&mesh_stats, &estimate));
+}
+
 fn spawn_camera_and_light(mut commands: Commands) {
     // Position camera to look at origin
     let camera_pos = Vec3::new(65.0, 40.0, 65.0);
@@ -0,0 +1,243 @@
+use bevy::{log::info, math::DVec3};
+use csgrs::traits::CSG;
+use geo::{Area, CoordsIter};
+
+use crate::{
+    bird::{BirdGenInputs, BirdParts, CSGMesh},
+    remesh::IndexedMesh,
+    slices::bird_section,
+    stats::MeshStats,
+};
+
+const MOULD_SEGMENTS: usize = 24;
+// How many places along each axis get tried when looking for the widest section
+const PARTING_SAMPLES: usize = 24;
+// Registration keys are balls this big compared to the wall, sat in the middle of the wall
+const KEY_RADIUS_RATIO: f64 = 0.3;
+const VENT_DIAMETER: f64 = 2.0;
+// Vents don't go closer than this to the sprue, or to each other
+const VENT_SPACING: f64 = 10.0;
+// A high point has to stand this far (in mm) above everywhere round it that leads somewhere
+// higher, or the air just runs off uphill and it isn't worth a vent
+const MIN_VENT_PROMINENCE: f64 = 1.0;
+// Pour cup on top of the sprue, compared to the sprue
+const FUNNEL_RATIO: f64 = 2.5;
+// Gap between the two halves, laid out on the bed
+const HALF_GAP: f64 = 10.0;
+// Anything cutting into the mould pokes out this far past it, so no paper thin skins get left
+const OVERLAP: f64 = 0.5;
+
+fn box_between(min: DVec3, max: DVec3) -> CSGMesh {
+    let size = max - min;
+    CSGMesh::cuboid(size.x, size.y, size.z, None).translate(min.x, min.y, min.z)
+}
+
+// Upright hole of `radius` from `at` up out through the top of the block
+fn upright_hole(at: DVec3, radius: f64, top: f64) -> CSGMesh {
+    CSGMesh::cylinder(radius, top - at.z + OVERLAP, MOULD_SEGMENTS, None)
+        .translate(at.x, at.y, at.z)
+}
+
+// Where the mould splits: whichever plane across the width or the height cuts through the
+// most bird (front to back would leave the beak and tail in one half). Gives (axis, offset).
+fn parting_plane(meshes: &[IndexedMesh], stats: &MeshStats) -> (usize, f64) {
+    let (min, max) = (stats.bounding_box_min, stats.bounding_box_max);
+    [1, 2]
+        .into_iter()
+        .flat_map(|axis| {
+            (1..PARTING_SAMPLES).map(move |sample| {
+                let offset =
+                    min[axis] + (max[axis] - min[axis]) * sample as f64 / PARTING_SAMPLES as f64;
+                (axis, offset)
+            })
+        })
+        .map(|(axis, offset)| {
+            let area = bird_section(meshes, axis, offset).unsigned_area();
+            (axis, offset, area)
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(axis, offset, _)| (axis, offset))
+        .unwrap_or((1, (min.y + max.y) / 2.0))
+}
+
+// Points nothing around them is higher than, where air would get trapped, highest first. Little
+// bumps and ripples get left out: starting from each one, everything reachable without going more
+// than `MIN_VENT_PROMINENCE` lower mustn't get anywhere higher.
+fn high_points(mesh: &IndexedMesh) -> Vec<DVec3> {
+    let height = |vertex: usize| mesh.vertices[vertex].z;
+    let mut neighbours = vec![Vec::new(); mesh.vertices.len()];
+    for triangle in mesh.triangles.iter() {
+        for corner in 0..3 {
+            let (from, to) = (triangle[corner], triangle[(corner + 1) % 3]);
+            neighbours[from].push(to);
+            neighbours[to].push(from);
+        }
+    }
+    let prominent = |peak: usize| {
+        let floor = height(peak) - MIN_VENT_PROMINENCE;
+        let mut seen = vec![false; mesh.vertices.len()];
+        let mut stack = vec![peak];
+        seen[peak] = true;
+        while let Some(vertex) = stack.pop() {
+            for next in neighbours[vertex].iter() {
+                if seen[*next] || height(*next) < floor {
+                    continue;
+                }
+                if height(*next) > height(peak) {
+                    return false;
+                }
+                seen[*next] = true;
+                stack.push(*next);
+            }
+        }
+        true
+    };
+
+    let mut points: Vec<DVec3> = (0..mesh.vertices.len())
+        .filter(|vertex| {
+            !neighbours[*vertex].is_empty()
+                && neighbours[*vertex]
+                    .iter()
+                    .all(|other| height(*other) <= height(*vertex))
+        })
+        .filter(|vertex| prominent(*vertex))
+        .map(|vertex| mesh.vertices[vertex])
+        .collect();
+    points.sort_by(|a, b| b.z.total_cmp(&a.z));
+    points
+}
+
+// Lay a half on the bed, parting face up, starting at `x`
+fn on_bed(half: &CSGMesh, x: f64) -> CSGMesh {
+    let min = MeshStats::from_meshes(&[half]).bounding_box_min;
+    half.translate(x - min.x, -min.y, -min.z)
+}
+
+// Two part mould for casting the bird: a block round it split at its widest section, with
+// registration keys on one half and sockets in the other, a sprue with a pour cup coming down
+// onto the top of the bird, and vents up from anywhere air would get trapped. Both halves come
+// back laid out for printing, parting face up.
+pub fn bird_mould(parts: &BirdParts, input: &BirdGenInputs) -> [CSGMesh; 2] {
+    let bird = parts.solid();
+    let stats = MeshStats::from_meshes(&[&bird]);
+    info!(
+        "The mould takes about {:.0}ml to fill",
+        stats.volume / 1000.0
+    );
+    // the parts overlap, so only the outside of them all together counts for the cavity
+    let meshes = [IndexedMesh::from_csg(&bird)];
+    let (axis, offset) = parting_plane(&meshes, &stats);
+
+    let wall = input.mould_wall as f64;
+    let block_min = stats.bounding_box_min - wall;
+    let block_max = stats.bounding_box_max + wall;
+    let mut below_max = block_max + OVERLAP;
+    below_max[axis] = offset;
+    let mut above_min = block_min - OVERLAP;
+    above_min[axis] = offset;
+    let below = box_between(block_min - OVERLAP, below_max);
+    let above = box_between(above_min, block_max + OVERLAP);
+
+    let mut mould = box_between(block_min, block_max).difference(&bird);
+
+    // sprue down onto the highest bit of bird on the parting plane (or just the highest bit, when
+    // the mould splits top and bottom), so both halves get half of it
+    let sprue_radius = input.sprue_diameter as f64 / 2.0;
+    let sprue_top = if axis == 1 {
+        bird_section(&meshes, axis, offset)
+            .coords_iter()
+            .max_by(|a, b| a.y.total_cmp(&b.y))
+            .map(|top| DVec3::new(top.x, offset, top.y))
+    } else {
+        high_points(&meshes[0]).first().copied()
+    }
+    .unwrap_or(DVec3::new(
+        (block_min.x + block_max.x) / 2.0,
+        (block_min.y + block_max.y) / 2.0,
+        stats.bounding_box_max.z,
+    ));
+    let funnel_depth = wall / 2.0;
+    let sprue = upright_hole(
+        sprue_top - DVec3::Z * sprue_radius,
+        sprue_radius,
+        block_max.z,
+    )
+    .union(
+        &CSGMesh::frustum(
+            sprue_radius,
+            sprue_radius * FUNNEL_RATIO,
+            funnel_depth + OVERLAP,
+            MOULD_SEGMENTS,
+            None,
+        )
+        .translate(sprue_top.x, sprue_top.y, block_max.z - funnel_depth),
+    );
+    mould = mould.difference(&sprue);
+
+    // vents from the highest places air could get stuck
+    let mut vented = vec![sprue_top];
+    for point in high_points(&meshes[0]) {
+        if vented.len() > input.vent_count.round() as usize {
+            break;
+        }
+        if vented
+            .iter()
+            .any(|other| (*other - point).truncate().length() < VENT_SPACING)
+        {
+            continue;
+        }
+        mould = mould.difference(&upright_hole(
+            point - DVec3::Z * VENT_DIAMETER,
+            VENT_DIAMETER / 2.0,
+            block_max.z,
+        ));
+        vented.push(point);
+    }
+
+    // registration keys in the corners of the parting face, half way into the wall
+    let key_radius = wall * KEY_RADIUS_RATIO;
+    let (across, up) = if axis == 1 { (0, 2) } else { (0, 1) };
+    let mut keys = CSGMesh::new();
+    let mut sockets = CSGMesh::new();
+    for (across_side, up_side) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        let mut centre = DVec3::ZERO;
+        centre[axis] = offset;
+        centre[across] = if across_side == 0 {
+            block_min[across] + wall / 2.0
+        } else {
+            block_max[across] - wall / 2.0
+        };
+        centre[up] = if up_side == 0 {
+            block_min[up] + wall / 2.0
+        } else {
+            block_max[up] - wall / 2.0
+        };
+        keys = keys.union(
+            &CSGMesh::sphere(key_radius, MOULD_SEGMENTS, MOULD_SEGMENTS / 2, None)
+                .translate(centre.x, centre.y, centre.z),
+        );
+        sockets = sockets.union(
+            &CSGMesh::sphere(
+                key_radius + input.key_clearance as f64,
+                MOULD_SEGMENTS,
+                MOULD_SEGMENTS / 2,
+                None,
+            )
+            .translate(centre.x, centre.y, centre.z),
+        );
+    }
+
+    let first = mould.intersection(&below).union(&keys.intersection(&above));
+    let second = mould.intersection(&above).difference(&sockets);
+
+    // turn the parting faces up
+    let (first, second) = if axis == 1 {
+        (first.rotate(90.0, 0.0, 0.0), second.rotate(-90.0, 0.0, 0.0))
+    } else {
+        (first, second.rotate(180.0, 0.0, 0.0))
+    };
+    let first = on_bed(&first, 0.0);
+    let first_width = MeshStats::from_meshes(&[&first]).bounding_box_max.x;
+    let second = on_bed(&second, first_width + HALF_GAP);
+    [first, second]
+}
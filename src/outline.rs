@@ -0,0 +1,197 @@
+use bevy::math::DVec3;
+use geo::{
+    BooleanOps, BoundingRect, Buffer, Coord, LineString, MultiPolygon, Polygon, Simplify,
+    unary_union,
+};
+
+use crate::{
+    bird::{BirdGenInputs, BirdParts, OutlineView},
+    remesh::IndexedMesh,
+};
+
+// Slivers smaller than this (in mm^2) get left out of the silhouette
+const MIN_TRIANGLE_AREA: f64 = 1e-6;
+// Parts with more triangles than this (smoothed or SDF ones) get decimated before they're
+// flattened, unioning every one of their triangles would take ages
+const SILHOUETTE_MAX_FACES: usize = 2000;
+// How far the outline can drift when tidying up the points along straight runs
+const OUTLINE_TOLERANCE: f64 = 0.02;
+// Gap left around the outline on the page
+const PAGE_MARGIN: f64 = 5.0;
+
+// Flatten a point onto the view, keeping the bird the right way up
+pub fn project(point: DVec3, view: OutlineView) -> Coord<f64> {
+    match view {
+        // from the bird's left, beak pointing left
+        OutlineView::Side => Coord {
+            x: point.x,
+            y: point.z,
+        },
+        // from above, beak pointing left
+        OutlineView::Top => Coord {
+            x: point.x,
+            y: point.y,
+        },
+        // beak on
+        OutlineView::Front => Coord {
+            x: -point.y,
+            y: point.z,
+        },
+    }
+}
+
+// Silhouette of one part: the triangles facing the view get flattened onto it and unioned
+// together (the ones facing away are behind them, so they can't add anything)
+fn part_silhouette(mesh: &IndexedMesh, view: OutlineView) -> MultiPolygon<f64> {
+    let triangles: Vec<Polygon<f64>> = mesh
+        .triangles
+        .iter()
+        .filter_map(|triangle| {
+            let corners = triangle.map(|vertex| project(mesh.vertices[vertex], view));
+            // every view is looking down on its flattened axes, so facing it is anticlockwise
+            let area = (corners[1] - corners[0]).x * (corners[2] - corners[0]).y
+                - (corners[2] - corners[0]).x * (corners[1] - corners[0]).y;
+            (area > MIN_TRIANGLE_AREA)
+                .then(|| Polygon::new(LineString::from(corners.to_vec()), vec![]))
+        })
+        .collect();
+    unary_union(&triangles)
+}
+
+// Silhouette of the whole bird seen from one side, each part's own silhouette unioned together
+pub fn bird_silhouette(parts: &BirdParts, view: OutlineView) -> MultiPolygon<f64> {
+    let silhouettes: Vec<MultiPolygon<f64>> = parts
+        .meshes()
+        .into_iter()
+        .map(|mesh| {
+            let indexed = IndexedMesh::from_csg(mesh);
+            if indexed.triangles.len() > SILHOUETTE_MAX_FACES {
+                part_silhouette(&indexed.decimate(SILHOUETTE_MAX_FACES), view)
+            } else {
+                part_silhouette(&indexed, view)
+            }
+        })
+        .collect();
+    unary_union(silhouettes.iter().flat_map(|silhouette| silhouette.iter()))
+        .simplify(OUTLINE_TOLERANCE)
+}
+
+// What gets exported: the silhouette itself, or a cookie cutter wall standing around it
+pub fn bird_outline(parts: &BirdParts, input: &BirdGenInputs) -> MultiPolygon<f64> {
+    let silhouette = bird_silhouette(parts, input.outline_view);
+    if input.cookie_cutter {
+        silhouette
+            .buffer(input.cutter_wall as f64)
+            .difference(&silhouette)
+    } else {
+        silhouette
+    }
+}
+
+// SVG path data for a polygon and its holes, after moving every point onto the page
+pub fn svg_path(polygon: &Polygon<f64>, to_page: impl Fn(Coord<f64>) -> Coord<f64>) -> String {
+    std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .map(|ring| {
+            let points: Vec<String> = ring
+                .coords()
+                .map(|coord| {
+                    let coord = to_page(*coord);
+                    format!("{:.3} {:.3}", coord.x, coord.y)
+                })
+                .collect();
+            format!("M {} Z", points.join(" L "))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// An SVG page in mm (1 user unit is 1mm), ready for the cutter
+pub fn svg_document(width: f64, height: f64, content: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.3}mm\" height=\"{:.3}mm\" viewBox=\"0 0 {:.3} {:.3}\">\n{}</svg>\n",
+        width, height, width, height, content
+    )
+}
+
+// Shelf packing: tallest first, left to right in rows, a new row when one's full and a new sheet
+// when the rows run off the bottom. Takes the (width, height) of everything to place and gives
+// back each sheet as (which one, where its top left corner goes), `gap` apart and from the edges.
+pub fn shelf_pack(
+    sizes: &[(f64, f64)],
+    width: f64,
+    height: f64,
+    gap: f64,
+) -> Vec<Vec<(usize, Coord<f64>)>> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|a, b| sizes[*b].1.total_cmp(&sizes[*a].1));
+
+    let mut sheets: Vec<Vec<(usize, Coord<f64>)>> = vec![Vec::new()];
+    let mut cursor = Coord { x: gap, y: gap };
+    let mut row_height = 0.0;
+    for index in order {
+        let (item_width, item_height) = sizes[index];
+        if cursor.x + item_width + gap > width && cursor.x > gap {
+            cursor = Coord {
+                x: gap,
+                y: cursor.y + row_height + gap,
+            };
+            row_height = 0.0;
+        }
+        if cursor.y + item_height + gap > height && cursor.y > gap {
+            sheets.push(Vec::new());
+            cursor = Coord { x: gap, y: gap };
+            row_height = 0.0;
+        }
+        if let Some(sheet) = sheets.last_mut() {
+            sheet.push((index, cursor));
+        }
+        cursor.x += item_width + gap;
+        row_height = f64::max(row_height, item_height);
+    }
+    sheets
+}
+
+// The outline as hairline cut paths, on a page just big enough for it
+pub fn outline_svg(outline: &MultiPolygon<f64>) -> String {
+    let Some(bounds) = outline.bounding_rect() else {
+        return svg_document(0.0, 0.0, "");
+    };
+    let width = bounds.width() + PAGE_MARGIN * 2.0;
+    let height = bounds.height() + PAGE_MARGIN * 2.0;
+    // SVG has y going down the page
+    let to_page = |coord: Coord<f64>| Coord {
+        x: coord.x - bounds.min().x + PAGE_MARGIN,
+        y: bounds.max().y - coord.y + PAGE_MARGIN,
+    };
+    let mut content = String::new();
+    for polygon in outline.iter() {
+        content += &format!(
+            "  <path d=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"0.1\" fill-rule=\"evenodd\" />\n",
+            svg_path(polygon, to_page)
+        );
+    }
+    svg_document(width, height, &content)
+}
+
+// The outline as closed polylines in a plain R12 DXF, in mm (R12 has no units setting, so the
+// importer has to be told it's mm)
+pub fn outline_dxf(outline: &MultiPolygon<f64>) -> String {
+    let mut dxf = String::from("0\nSECTION\n2\nENTITIES\n");
+    for polygon in outline.iter() {
+        for ring in std::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+            dxf += "0\nPOLYLINE\n8\n0\n66\n1\n10\n0.0\n20\n0.0\n30\n0.0\n70\n1\n";
+            // closed polylines don't repeat the first point
+            let coords: Vec<&Coord<f64>> = ring.coords().collect();
+            for coord in &coords[..coords.len().saturating_sub(1)] {
+                dxf += &format!(
+                    "0\nVERTEX\n8\n0\n10\n{:.4}\n20\n{:.4}\n30\n0.0\n",
+                    coord.x, coord.y
+                );
+            }
+            dxf += "0\nSEQEND\n8\n0\n";
+        }
+    }
+    dxf += "0\nENDSEC\n0\nEOF\n";
+    dxf
+}
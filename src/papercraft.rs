@@ -0,0 +1,572 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bevy::{
+    log::warn,
+    math::{DVec2, DVec3},
+};
+use csgrs::traits::CSG;
+
+use crate::{
+    bird::{BirdGenInputs, BirdPart, BirdParts, CSGMesh, PaperSize},
+    outline::{shelf_pack, svg_document},
+    remesh::IndexedMesh,
+};
+
+// Border round the page (most printers can't print right to the edge), also the gap between nets
+const PAGE_MARGIN: f64 = 10.0;
+const TAB_WIDTH: f64 = 5.0;
+const LABEL_SIZE: f64 = 2.5;
+// Triangles get shrunk this much before checking for overlaps, so neighbours can share an edge
+const OVERLAP_SHRINK: f64 = 1e-3;
+// Slivers smaller than this (in mm^2) get left out, there's no folding them anyway
+const MIN_FACE_AREA: f64 = 1e-4;
+// A small head still gets a few faces
+const MIN_PART_FACES: usize = 8;
+// Only the head and body get nets, with everything stuck on them unioned in so they fold up closed
+const PAPER_PARTS: [BirdPart; 2] = [BirdPart::Body, BirdPart::Head];
+
+impl PaperSize {
+    // width and height in mm, portrait
+    fn size(&self) -> DVec2 {
+        match self {
+            PaperSize::A4 => DVec2::new(210.0, 297.0),
+            PaperSize::Letter => DVec2::new(215.9, 279.4),
+        }
+    }
+}
+
+// Triangles of the mesh flattened out into one connected piece of paper
+struct Net {
+    part: BirdPart,
+    // mesh triangle, with its corners on the paper (in mm, y up) in the same order
+    faces: Vec<(usize, [DVec2; 3])>,
+    // edges that get folded rather than cut (lower vertex first)
+    folds: HashSet<(usize, usize)>,
+    // glue tabs, by the face and edge they hang off, on the paper like the faces
+    tabs: HashMap<(usize, (usize, usize)), [DVec2; 4]>,
+}
+
+// Where the third corner of a triangle lands on the paper, given where the other two went.
+// It goes on the far side of the edge from `away_from` (the face it's being unfolded off).
+fn third_corner(
+    from: (DVec2, DVec3),
+    to: (DVec2, DVec3),
+    corner: DVec3,
+    away_from: Option<DVec2>,
+) -> DVec2 {
+    let edge = (to.1 - from.1).normalize();
+    let along = (corner - from.1).dot(edge);
+    let height = ((corner - from.1) - edge * along).length();
+    let flat_edge = (to.0 - from.0).normalize();
+    let mut out = flat_edge.perp();
+    if let Some(away_from) = away_from
+        && out.dot(away_from - from.0) > 0.0
+    {
+        out = -out;
+    }
+    from.0 + flat_edge * along + out * height
+}
+
+// Separating axis test, with both triangles shrunk a touch so ones sharing an edge don't count
+fn triangles_overlap(a: &[DVec2; 3], b: &[DVec2; 3]) -> bool {
+    let shrink = |triangle: &[DVec2; 3]| {
+        let centre = (triangle[0] + triangle[1] + triangle[2]) / 3.0;
+        triangle.map(|corner| centre + (corner - centre) * (1.0 - OVERLAP_SHRINK))
+    };
+    let (a, b) = (shrink(a), shrink(b));
+    for triangle in [&a, &b] {
+        for corner in 0..3 {
+            let axis = (triangle[(corner + 1) % 3] - triangle[corner]).perp();
+            let extent = |triangle: &[DVec2; 3]| {
+                triangle
+                    .iter()
+                    .map(|point| axis.dot(*point))
+                    .fold((f64::MAX, f64::MIN), |(low, high), value| {
+                        (low.min(value), high.max(value))
+                    })
+            };
+            let ((a_low, a_high), (b_low, b_high)) = (extent(&a), extent(&b));
+            if a_high <= b_low || b_high <= a_low {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn bounds<'a>(points: impl Iterator<Item = &'a DVec2>) -> (DVec2, DVec2) {
+    points.fold(
+        (DVec2::splat(f64::MAX), DVec2::splat(f64::MIN)),
+        |(min, max), point| (min.min(*point), max.max(*point)),
+    )
+}
+
+// Every edge (lower vertex first) with the faces either side of it
+fn edge_faces(mesh: &IndexedMesh) -> HashMap<(usize, usize), Vec<usize>> {
+    let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (face, triangle) in mesh.triangles.iter().enumerate() {
+        for corner in 0..3 {
+            let (from, to) = (triangle[corner], triangle[(corner + 1) % 3]);
+            edges
+                .entry((from.min(to), from.max(to)))
+                .or_default()
+                .push(face);
+        }
+    }
+    edges
+}
+
+// Flatten the mesh out into nets. Each net starts from the biggest face left and grows breadth
+// first across the edges, as long as the new face doesn't land on one that's already there and
+// the net still fits on the page. Anything that doesn't fit ends up starting a net of its own.
+fn unfold(
+    mesh: &IndexedMesh,
+    edges: &HashMap<(usize, usize), Vec<usize>>,
+    part: BirdPart,
+    max_size: DVec2,
+) -> Vec<Net> {
+    let corners = |face: usize| mesh.triangles[face].map(|vertex| mesh.vertices[vertex]);
+    let area = |face: usize| {
+        let [a, b, c] = corners(face);
+        (b - a).cross(c - a).length() / 2.0
+    };
+    let mut placed: Vec<bool> = (0..mesh.triangles.len())
+        .map(|face| area(face) < MIN_FACE_AREA)
+        .collect();
+    let mut order: Vec<usize> = (0..mesh.triangles.len()).collect();
+    order.sort_by(|a, b| area(*b).total_cmp(&area(*a)));
+
+    let mut nets = Vec::new();
+    for start in order {
+        if placed[start] {
+            continue;
+        }
+        placed[start] = true;
+        let [a, b, c] = corners(start);
+        let flat_a = DVec2::ZERO;
+        let flat_b = DVec2::new((b - a).length(), 0.0);
+        let first = [
+            flat_a,
+            flat_b,
+            third_corner((flat_a, a), (flat_b, b), c, None),
+        ];
+        let mut net = Net {
+            part,
+            faces: vec![(start, first)],
+            folds: HashSet::new(),
+            tabs: HashMap::new(),
+        };
+
+        let mut queue = VecDeque::from([0]);
+        while let Some(net_face) = queue.pop_front() {
+            let (face, flat) = net.faces[net_face];
+            let triangle = mesh.triangles[face];
+            for corner in 0..3 {
+                let (from, to) = (triangle[corner], triangle[(corner + 1) % 3]);
+                let edge = (from.min(to), from.max(to));
+                // only unfold across clean edges, with exactly one face each side
+                let [first_face, second_face] = edges[&edge][..] else {
+                    continue;
+                };
+                let neighbour = if first_face == face {
+                    second_face
+                } else {
+                    first_face
+                };
+                if placed[neighbour] {
+                    continue;
+                }
+                let neighbour_flat = mesh.triangles[neighbour].map(|vertex| {
+                    if vertex == from {
+                        flat[corner]
+                    } else if vertex == to {
+                        flat[(corner + 1) % 3]
+                    } else {
+                        third_corner(
+                            (flat[corner], mesh.vertices[from]),
+                            (flat[(corner + 1) % 3], mesh.vertices[to]),
+                            mesh.vertices[vertex],
+                            Some(flat[(corner + 2) % 3]),
+                        )
+                    }
+                });
+                let (min, max) = bounds(
+                    net.faces
+                        .iter()
+                        .flat_map(|(_, flat)| flat.iter())
+                        .chain(neighbour_flat.iter()),
+                );
+                let size = max - min;
+                if size.x > max_size.x
+                    || size.y > max_size.y
+                    || net
+                        .faces
+                        .iter()
+                        .any(|(_, flat)| triangles_overlap(flat, &neighbour_flat))
+                {
+                    continue;
+                }
+                placed[neighbour] = true;
+                net.folds.insert(edge);
+                net.faces.push((neighbour, neighbour_flat));
+                queue.push_back(net.faces.len() - 1);
+            }
+        }
+        nets.push(net);
+    }
+    nets
+}
+
+// A glue tab off the edge from `start` to `end`, on the other side from `inside`
+fn tab_shape(start: DVec2, end: DVec2, inside: DVec2) -> [DVec2; 4] {
+    let along = (end - start).normalize_or_zero();
+    let mut out = along.perp();
+    if out.dot(inside - start) > 0.0 {
+        out = -out;
+    }
+    let depth = TAB_WIDTH.min((end - start).length() * 0.4);
+    [
+        start,
+        start + along * depth + out * depth,
+        end - along * depth + out * depth,
+        end,
+    ]
+}
+
+// Tabs are four sided, so split them in two for the overlap test
+fn tab_triangles(tab: &[DVec2; 4]) -> [[DVec2; 3]; 2] {
+    [[tab[0], tab[1], tab[2]], [tab[0], tab[2], tab[3]]]
+}
+
+// One glue tab for every numbered edge. It goes on the lowest numbered face if it fits there,
+// otherwise on the face the other side. Fitting means not landing on a face or another tab of
+// the same net (they'd get cut off each other). If it won't fit either side the edge just gets
+// the numbers, to be taped up instead.
+fn place_tabs(
+    nets: &mut [Net],
+    mesh: &IndexedMesh,
+    edges: &HashMap<(usize, usize), Vec<usize>>,
+    numbers: &HashMap<(usize, usize), usize>,
+) {
+    let mut net_of: HashMap<usize, (usize, usize)> = HashMap::new();
+    for (net_index, net) in nets.iter().enumerate() {
+        for (face_index, (face, _)) in net.faces.iter().enumerate() {
+            net_of.insert(*face, (net_index, face_index));
+        }
+    }
+    let mut numbered: Vec<((usize, usize), usize)> = numbers
+        .iter()
+        .map(|(edge, number)| (*edge, *number))
+        .collect();
+    numbered.sort_by_key(|(_, number)| *number);
+
+    let mut untabbed = 0;
+    for (edge, _) in numbered {
+        let mut sides = edges[&edge].clone();
+        sides.sort();
+        let placed = sides.iter().any(|face| {
+            let Some(&(net_index, face_index)) = net_of.get(face) else {
+                return false;
+            };
+            let net = &mut nets[net_index];
+            let flat = net.faces[face_index].1;
+            let triangle = mesh.triangles[*face];
+            let Some(corner) = (0..3).find(|corner| {
+                let (from, to) = (triangle[*corner], triangle[(*corner + 1) % 3]);
+                (from.min(to), from.max(to)) == edge
+            }) else {
+                return false;
+            };
+            let tab = tab_shape(flat[corner], flat[(corner + 1) % 3], flat[(corner + 2) % 3]);
+            let overlaps = tab_triangles(&tab).iter().any(|piece| {
+                net.faces
+                    .iter()
+                    .any(|(_, flat)| triangles_overlap(flat, piece))
+                    || net.tabs.values().any(|other| {
+                        tab_triangles(other)
+                            .iter()
+                            .any(|other| triangles_overlap(other, piece))
+                    })
+            });
+            if !overlaps {
+                net.tabs.insert((*face, edge), tab);
+            }
+            !overlaps
+        });
+        if !placed {
+            untabbed += 1;
+        }
+    }
+    if untabbed > 0 {
+        warn!("{untabbed} papercraft edges have no room for a glue tab, tape those up instead");
+    }
+}
+
+// A net drawn out, with its top left corner at 0, 0 (and y going down the page)
+struct DrawnNet {
+    content: String,
+    size: DVec2,
+}
+
+// Cut lines solid, folds dashed (valley) or dash dotted (mountain), glue tabs in grey where
+// `place_tabs` put them and the same number printed on both sides so they can be matched up
+fn draw_net(
+    net: &Net,
+    mesh: &IndexedMesh,
+    edges: &HashMap<(usize, usize), Vec<usize>>,
+    numbers: &HashMap<(usize, usize), usize>,
+) -> DrawnNet {
+    let (min, max) = bounds(net.faces.iter().flat_map(|(_, flat)| flat.iter()));
+    // room for the tabs sticking out
+    let (min, max) = (min - TAB_WIDTH, max + TAB_WIDTH);
+    let to_page = |point: DVec2| DVec2::new(point.x - min.x, max.y - point.y);
+    let line = |from: DVec2, to: DVec2, dashes: &str| {
+        format!(
+            "  <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\" stroke=\"black\" stroke-width=\"0.2\"{} />\n",
+            from.x, from.y, to.x, to.y, dashes
+        )
+    };
+    // a fold is a valley if the face on the other side tips up towards this one's outside
+    let valley = |face: usize, edge: (usize, usize)| {
+        let [a, b, c] = mesh.triangles[face].map(|vertex| mesh.vertices[vertex]);
+        let normal = (b - a).cross(c - a);
+        edges[&edge].iter().any(|other| {
+            mesh.triangles[*other]
+                .iter()
+                .any(|vertex| normal.dot(mesh.vertices[*vertex] - a) > 1e-9)
+        })
+    };
+    const VALLEY: &str = " stroke-dasharray=\"2 1\"";
+    const MOUNTAIN: &str = " stroke-dasharray=\"3 1 0.5 1\"";
+
+    let mut content = String::new();
+    for (face, flat) in net.faces.iter() {
+        let triangle = mesh.triangles[*face];
+        let page = flat.map(to_page);
+        for corner in 0..3 {
+            let (from, to) = (triangle[corner], triangle[(corner + 1) % 3]);
+            let edge = (from.min(to), from.max(to));
+            let (start, end) = (page[corner], page[(corner + 1) % 3]);
+            let lowest_face = edges[&edge].iter().min() == Some(face);
+            let fold_dashes = if valley(*face, edge) {
+                VALLEY
+            } else {
+                MOUNTAIN
+            };
+            if net.folds.contains(&edge) {
+                // both faces have the fold, only draw it the once
+                if lowest_face {
+                    content += &line(start, end, fold_dashes);
+                }
+                continue;
+            }
+
+            let mut out = (end - start).normalize_or_zero().perp();
+            if out.dot(page[(corner + 2) % 3] - start) > 0.0 {
+                out = -out;
+            }
+            let Some(number) = numbers.get(&edge) else {
+                // an edge with nothing to glue to just gets cut
+                content += &line(start, end, "");
+                continue;
+            };
+            if let Some(tab) = net.tabs.get(&(*face, edge)) {
+                // the tab folds under the face on the other side
+                let tab = tab.map(to_page);
+                content += &format!(
+                    "  <polygon points=\"{}\" fill=\"#dddddd\" stroke=\"black\" stroke-width=\"0.2\" />\n",
+                    tab.iter()
+                        .map(|point| format!("{:.3},{:.3}", point.x, point.y))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                );
+                content += &line(start, end, fold_dashes);
+            } else {
+                content += &line(start, end, "");
+            }
+            let label = (start + end) / 2.0 - out * LABEL_SIZE;
+            content += &format!(
+                "  <text x=\"{:.3}\" y=\"{:.3}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+                label.x, label.y, LABEL_SIZE, number
+            );
+        }
+    }
+    // which part it is, in the middle of the first (biggest) face
+    if let Some((_, flat)) = net.faces.first() {
+        let centre = to_page((flat[0] + flat[1] + flat[2]) / 3.0);
+        content += &format!(
+            "  <text x=\"{:.3}\" y=\"{:.3}\" font-family=\"sans-serif\" font-size=\"{}\" fill=\"grey\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+            centre.x,
+            centre.y,
+            LABEL_SIZE * 1.5,
+            net.part.name()
+        );
+    }
+    DrawnNet {
+        content,
+        size: max - min,
+    }
+}
+
+// The head and body decimated down to a paper model, unfolded into nets and laid out on pages,
+// one SVG each. Each part gets its own nets, so they can go on different coloured paper.
+pub fn bird_papercraft_pages(parts: &BirdParts, input: &BirdGenInputs) -> Vec<String> {
+    let page = input.paper_size.size();
+    let max_net_size = page - (PAGE_MARGIN + TAB_WIDTH) * 2.0;
+    let scale = input.paper_scale as f64 / 100.0;
+
+    // the beak, eyes and head markings go with the head, the rest with the body (all of it, if the
+    // head's been joined onto the body). Welding mends any T-junctions, so every edge has a face
+    // either side to fold or glue to.
+    let has_head = parts.iter().any(|(part, _)| part == BirdPart::Head);
+    let paper_part = |part: BirdPart| {
+        if has_head && part.on_head() {
+            BirdPart::Head
+        } else {
+            BirdPart::Body
+        }
+    };
+    let meshes: Vec<(BirdPart, IndexedMesh)> = PAPER_PARTS
+        .into_iter()
+        .filter(|group| *group == BirdPart::Body || has_head)
+        .map(|group| {
+            let solid = parts
+                .iter()
+                .filter(|(part, _)| paper_part(*part) == group)
+                .fold(CSGMesh::new(), |solid, (_, mesh)| solid.union(mesh));
+            (group, IndexedMesh::from_csg(&solid))
+        })
+        .collect();
+    let total_faces: usize = meshes
+        .iter()
+        .map(|(_, mesh)| mesh.triangles.len())
+        .sum::<usize>()
+        .max(1);
+
+    let mut drawn = Vec::new();
+    // edges get numbered right through the whole bird
+    let mut next_number = 1;
+    for (part, mesh) in meshes {
+        let share = mesh.triangles.len() as f64 / total_faces as f64;
+        let target = ((input.paper_faces as f64 * share) as usize).max(MIN_PART_FACES);
+        let mut paper_mesh = mesh.decimate(target);
+        for vertex in paper_mesh.vertices.iter_mut() {
+            *vertex *= scale;
+        }
+
+        let edges = edge_faces(&paper_mesh);
+        let mut nets = unfold(&paper_mesh, &edges, part, max_net_size);
+        let mut numbers: HashMap<(usize, usize), usize> = HashMap::new();
+        for net in nets.iter() {
+            for (face, _) in net.faces.iter() {
+                let triangle = paper_mesh.triangles[*face];
+                for corner in 0..3 {
+                    let (from, to) = (triangle[corner], triangle[(corner + 1) % 3]);
+                    let edge = (from.min(to), from.max(to));
+                    if !net.folds.contains(&edge)
+                        && edges[&edge].len() == 2
+                        && !numbers.contains_key(&edge)
+                    {
+                        numbers.insert(edge, next_number);
+                        next_number += 1;
+                    }
+                }
+            }
+        }
+        place_tabs(&mut nets, &paper_mesh, &edges, &numbers);
+        drawn.extend(
+            nets.iter()
+                .map(|net| draw_net(net, &paper_mesh, &edges, &numbers)),
+        );
+    }
+
+    if drawn.iter().any(|net| {
+        net.size.x > page.x - PAGE_MARGIN * 2.0 || net.size.y > page.y - PAGE_MARGIN * 2.0
+    }) {
+        warn!("Some of the papercraft faces are bigger than the page, try a smaller scale");
+    }
+    let sizes: Vec<(f64, f64)> = drawn.iter().map(|net| (net.size.x, net.size.y)).collect();
+    shelf_pack(&sizes, page.x, page.y, PAGE_MARGIN)
+        .iter()
+        .map(|sheet| {
+            let content: String = sheet
+                .iter()
+                .map(|(index, place)| {
+                    format!(
+                        "<g transform=\"translate({:.3} {:.3})\">\n{}</g>\n",
+                        place.x, place.y, drawn[*index].content
+                    )
+                })
+                .collect();
+            svg_document(page.x, page.y, &content)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remesh::tests::octahedron;
+
+    #[test]
+    fn overlapping_triangles() {
+        let triangle = [DVec2::ZERO, DVec2::new(10.0, 0.0), DVec2::new(0.0, 10.0)];
+        // sharing an edge doesn't count
+        let neighbour = [
+            DVec2::new(10.0, 0.0),
+            DVec2::new(10.0, 10.0),
+            DVec2::new(0.0, 10.0),
+        ];
+        assert!(!triangles_overlap(&triangle, &neighbour));
+        assert!(!triangles_overlap(
+            &triangle,
+            &triangle.map(|corner| corner + DVec2::new(20.0, 0.0))
+        ));
+        assert!(triangles_overlap(
+            &triangle,
+            &triangle.map(|corner| corner + DVec2::new(2.0, 2.0))
+        ));
+        // one right inside the other
+        let inside = [
+            DVec2::new(1.0, 1.0),
+            DVec2::new(3.0, 1.0),
+            DVec2::new(1.0, 3.0),
+        ];
+        assert!(triangles_overlap(&triangle, &inside));
+    }
+
+    #[test]
+    fn unfolds_every_face_once_without_overlaps() {
+        let mesh = octahedron(10.0).loop_subdivide(|_| false);
+        let edges = edge_faces(&mesh);
+        // a big page fits it all in a net or two, a small one splits it up more
+        for page in [1000.0, 15.0] {
+            let nets = unfold(&mesh, &edges, BirdPart::Body, DVec2::splat(page));
+            let mut faces: Vec<usize> = nets
+                .iter()
+                .flat_map(|net| net.faces.iter().map(|(face, _)| *face))
+                .collect();
+            faces.sort();
+            assert_eq!(faces, (0..mesh.triangles.len()).collect::<Vec<_>>());
+
+            for net in nets.iter() {
+                for (index, (face, flat)) in net.faces.iter().enumerate() {
+                    // flattening doesn't stretch anything
+                    let triangle = mesh.triangles[*face];
+                    for corner in 0..3 {
+                        let next = (corner + 1) % 3;
+                        let length = (mesh.vertices[triangle[next]]
+                            - mesh.vertices[triangle[corner]])
+                            .length();
+                        assert!(((flat[next] - flat[corner]).length() - length).abs() < 1e-6);
+                    }
+                    for (_, other) in net.faces[index + 1..].iter() {
+                        assert!(!triangles_overlap(flat, other));
+                    }
+                }
+                let (min, max) = bounds(net.faces.iter().flat_map(|(_, flat)| flat.iter()));
+                assert!(max.x - min.x <= page && max.y - min.y <= page);
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::math::DVec3;
+use csgrs::{
+    mesh::{polygon::Polygon as Face, vertex::Vertex},
+    traits::CSG,
+};
+use geo::{Buffer, CoordsIter, MultiPolygon, Orient, TriangulateEarcut, orient::Direction};
+
+use crate::{
+    bird::{BirdGenInputs, BirdParts, CSGMesh, OutlineView},
+    outline::bird_silhouette,
+    stats::MeshStats,
+};
+
+const LOOP_SEGMENTS: usize = 24;
+// Wall left round the jump ring hole
+const LOOP_WALL: f64 = 1.5;
+// The hole pokes out this far past the top and bottom, so it cuts clean through
+const OVERLAP: f64 = 0.5;
+
+// The axis the relief gets squashed along, towards whoever's looking at it
+fn depth_axis(view: OutlineView) -> usize {
+    match view {
+        OutlineView::Front => 0,
+        OutlineView::Side => 1,
+        OutlineView::Top => 2,
+    }
+}
+
+// Squash a part along the view, then turn it so the side being looked at faces up. Afterwards
+// x and y on the bed are the same as the outline's (see `outline::project`).
+fn squash(mesh: &CSGMesh, view: OutlineView, depth_scale: f64) -> CSGMesh {
+    match view {
+        OutlineView::Side => mesh.scale(1.0, depth_scale, 1.0).rotate(-90.0, 0.0, 0.0),
+        OutlineView::Top => mesh.scale(1.0, 1.0, depth_scale),
+        OutlineView::Front => mesh.scale(depth_scale, 1.0, 1.0).rotate(0.0, 90.0, 90.0),
+    }
+}
+
+fn face(corners: &[DVec3]) -> Face<()> {
+    let normal = (corners[1] - corners[0])
+        .cross(corners[2] - corners[0])
+        .normalize_or_zero();
+    Face::new(
+        corners
+            .iter()
+            .map(|corner| {
+                Vertex::new(
+                    [corner.x, corner.y, corner.z].into(),
+                    [normal.x, normal.y, normal.z].into(),
+                )
+            })
+            .collect(),
+        None,
+    )
+}
+
+// Straight up extrusion of a 2D shape, from z 0 to `height`
+fn extrude(shape: &MultiPolygon<f64>, height: f64) -> CSGMesh {
+    let mut faces = Vec::new();
+    // outsides anticlockwise and holes clockwise, so the walls all face out
+    let shape = shape.orient(Direction::Default);
+    for polygon in shape.iter() {
+        for triangle in polygon.earcut_triangles() {
+            let [a, b, c] = triangle
+                .to_array()
+                .map(|corner| DVec3::new(corner.x, corner.y, 0.0));
+            let (b, c) = if (b - a).cross(c - a).z < 0.0 {
+                (c, b)
+            } else {
+                (b, c)
+            };
+            let up = DVec3::Z * height;
+            faces.push(face(&[a + up, b + up, c + up]));
+            faces.push(face(&[a, c, b]));
+        }
+        for ring in std::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+            for line in ring.lines() {
+                let from = DVec3::new(line.start.x, line.start.y, 0.0);
+                let to = DVec3::new(line.end.x, line.end.y, 0.0);
+                let up = DVec3::Z * height;
+                faces.push(face(&[from, to, to + up, from + up]));
+            }
+        }
+    }
+    CSGMesh::from_polygons(&faces, None)
+}
+
+// Flatten the bird into a relief for plaques and pendants: squashed along the view down to the
+// relief depth and laid face up, optionally on a backing plate following its outline, with a
+// loop at the top for a jump ring. It all gets unioned into the body, so it prints (and exports)
+// as one piece and the jump ring hole goes through everything in its way.
+pub fn make_relief(parts: &mut BirdParts, input: &BirdGenInputs) {
+    let view = input.outline_view;
+    let silhouette = bird_silhouette(parts, view);
+    let stats = MeshStats::from_meshes(&parts.meshes());
+    let axis = depth_axis(view);
+    let depth = stats.bounding_box_max[axis] - stats.bounding_box_min[axis];
+    let depth_scale = input.relief_depth as f64 / depth.max(f64::EPSILON);
+    for mesh in parts.meshes_mut() {
+        *mesh = squash(mesh, view, depth_scale);
+    }
+
+    // the back of the relief sits on the plate
+    let plate_thickness = input.plate_thickness as f64;
+    let base = if input.relief_plate {
+        plate_thickness
+    } else {
+        0.0
+    };
+    let floor = MeshStats::from_meshes(&parts.meshes()).bounding_box_min.z;
+    for mesh in parts.meshes_mut() {
+        *mesh = mesh.translate(0.0, 0.0, base - floor);
+    }
+
+    let border = if input.relief_plate {
+        input.plate_border as f64
+    } else {
+        0.0
+    };
+    let mut backing = input
+        .relief_plate
+        .then(|| extrude(&silhouette.buffer(border), plate_thickness));
+    let mut hole = None;
+    if input.jump_ring
+        && let Some(top) = silhouette.coords_iter().max_by(|a, b| a.y.total_cmp(&b.y))
+    {
+        // loop sticking up off the top of the outline (or the plate), as thick as the plate
+        let hole_radius = input.jump_ring_diameter as f64 / 2.0;
+        let centre = DVec3::new(top.x, top.y + border + hole_radius, 0.0);
+        let bail = CSGMesh::cylinder(
+            hole_radius + LOOP_WALL,
+            plate_thickness,
+            LOOP_SEGMENTS,
+            None,
+        )
+        .translate(centre.x, centre.y, 0.0);
+        backing = Some(match backing {
+            Some(plate) => plate.union(&bail),
+            None => bail,
+        });
+        hole = Some(
+            CSGMesh::cylinder(
+                hole_radius,
+                base + input.relief_depth as f64 + OVERLAP * 2.0,
+                LOOP_SEGMENTS,
+                None,
+            )
+            .translate(centre.x, centre.y, -OVERLAP),
+        );
+    }
+    let mut relief = parts.solid();
+    if let Some(backing) = backing {
+        relief = relief.union(&backing);
+    }
+    if let Some(hole) = hole {
+        relief = relief.difference(&hole);
+    }
+    *parts = BirdParts {
+        body: relief,
+        head: CSGMesh::new(),
+        beak: CSGMesh::new(),
+        eyes: None,
+        pupils: None,
+        wings: None,
+        breast_patch: None,
+        wing_bars: None,
+        cheek_spots: None,
+        face_mask: None,
+        voxels: None,
+    };
+}
@@ -0,0 +1,714 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use bevy::math::{DMat3, DVec3};
+use csgrs::mesh::{polygon::Polygon, vertex::Vertex};
+
+use crate::{bird::CSGMesh, stats::mesh_triangles};
+
+// Vertices closer than this (in mm) get merged when building indexed meshes
+const WELD_TOLERANCE: f64 = 1e-4;
+// A vertex closer than this (in mm) to the middle of an edge counts as a T-junction on it
+const T_JUNCTION_TOLERANCE: f64 = 1e-3;
+
+// Taubin smoothing factors, shrink by lambda then grow back by mu (a bit more than lambda)
+const TAUBIN_LAMBDA: f64 = 0.5;
+const TAUBIN_MU: f64 = -0.53;
+
+// Triangles with shared vertices. The csgrs meshes are a soup of polygons, this is what we need
+// to know which triangles are neighbours (and what the 3MF wants, slicers complain about "open" edges otherwise)
+pub struct IndexedMesh {
+    pub vertices: Vec<DVec3>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl IndexedMesh {
+    pub fn from_csg(mesh: &CSGMesh) -> Self {
+        IndexedMesh::from_csg_parts(&[mesh]).0
+    }
+
+    // Several meshes welded into one (so where they touch they share vertices), along with which
+    // mesh each triangle came from. T-junctions get stitched up too, see `repair_t_junctions`.
+    pub fn from_csg_parts(meshes: &[&CSGMesh]) -> (Self, Vec<usize>) {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+        let mut triangle_parts = Vec::new();
+        let mut vertex_lookup: HashMap<[i64; 3], usize> = HashMap::new();
+        for (part, mesh) in meshes.iter().enumerate() {
+            for corners in mesh_triangles(mesh) {
+                let triangle = corners.map(|corner| {
+                    let key = (corner / WELD_TOLERANCE).round().as_i64vec3().to_array();
+                    *vertex_lookup.entry(key).or_insert_with(|| {
+                        vertices.push(corner);
+                        vertices.len() - 1
+                    })
+                });
+                // tiny slivers collapse when welding, just drop them
+                if triangle[0] != triangle[1]
+                    && triangle[1] != triangle[2]
+                    && triangle[0] != triangle[2]
+                {
+                    triangles.push(triangle);
+                    triangle_parts.push(part);
+                }
+            }
+        }
+        let mut indexed = IndexedMesh {
+            vertices,
+            triangles,
+        };
+        let sources = indexed.repair_t_junctions();
+        let triangle_parts = sources
+            .iter()
+            .map(|source| triangle_parts[*source])
+            .collect();
+        (indexed, triangle_parts)
+    }
+
+    // The csgrs booleans leave T-junctions all over: a vertex sitting partway along the edge of the
+    // triangle next door instead of at its corner, so that edge only has a triangle on one side.
+    // Every triangle with vertices like that along its edges gets split into a fan around its middle
+    // that takes them in, then the edges match up on both sides. Gives back which of the old
+    // triangles each new one came from.
+    fn repair_t_junctions(&mut self) -> Vec<usize> {
+        let edges: Vec<(usize, usize)> = self.edges().into_keys().collect();
+        if edges.is_empty() {
+            return (0..self.triangles.len()).collect();
+        }
+        // bucket the vertices in a grid about an edge long, then walk along each edge looking
+        // for vertices close to it
+        let cell_size = (edges
+            .iter()
+            .map(|(a, b)| self.vertices[*a].distance(self.vertices[*b]))
+            .sum::<f64>()
+            / edges.len() as f64)
+            .max(T_JUNCTION_TOLERANCE);
+        let cell = |point: DVec3| (point / cell_size).floor().as_i64vec3().to_array();
+        let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+        for (index, vertex) in self.vertices.iter().enumerate() {
+            grid.entry(cell(*vertex)).or_default().push(index);
+        }
+
+        // vertices partway along each edge, in order from the lower numbered end
+        let mut on_edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (a, b) in edges {
+            let (start, end) = (self.vertices[a], self.vertices[b]);
+            let along = end - start;
+            let length_squared = along.length_squared();
+            let steps = (along.length() / cell_size).ceil() as usize;
+            let mut found: Vec<(f64, usize)> = Vec::new();
+            let mut checked = HashSet::new();
+            for step in 0..=steps {
+                let [x, y, z] = cell(start + along * step as f64 / steps.max(1) as f64);
+                for neighbour in (-1..=1).flat_map(|dx| {
+                    (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| [x + dx, y + dy, z + dz]))
+                }) {
+                    for vertex in grid.get(&neighbour).into_iter().flatten() {
+                        if *vertex == a || *vertex == b || !checked.insert(*vertex) {
+                            continue;
+                        }
+                        let point = self.vertices[*vertex];
+                        let t = (point - start).dot(along) / length_squared;
+                        let closest = start + along * t;
+                        if t > 0.0 && t < 1.0 && closest.distance(point) < T_JUNCTION_TOLERANCE {
+                            found.push((t, *vertex));
+                        }
+                    }
+                }
+            }
+            if !found.is_empty() {
+                found.sort_by(|x, y| x.0.total_cmp(&y.0));
+                on_edges.insert(
+                    (a, b),
+                    found.into_iter().map(|(_, vertex)| vertex).collect(),
+                );
+            }
+        }
+        if on_edges.is_empty() {
+            return (0..self.triangles.len()).collect();
+        }
+
+        let mut triangles = Vec::new();
+        let mut sources = Vec::new();
+        for (source, triangle) in self.triangles.iter().enumerate() {
+            // the corners with whatever sits along the edges in between, in order round the triangle
+            let mut ring = Vec::new();
+            for (from, to) in [(0, 1), (1, 2), (2, 0)].map(|(i, j)| (triangle[i], triangle[j])) {
+                ring.push(from);
+                if let Some(between) = on_edges.get(&(from.min(to), from.max(to))) {
+                    if from < to {
+                        ring.extend(between.iter());
+                    } else {
+                        ring.extend(between.iter().rev());
+                    }
+                }
+            }
+            if ring.len() == 3 {
+                triangles.push(*triangle);
+                sources.push(source);
+                continue;
+            }
+            let middle = triangle
+                .map(|vertex| self.vertices[vertex])
+                .iter()
+                .sum::<DVec3>()
+                / 3.0;
+            self.vertices.push(middle);
+            let middle = self.vertices.len() - 1;
+            for (i, vertex) in ring.iter().enumerate() {
+                triangles.push([middle, *vertex, ring[(i + 1) % ring.len()]]);
+                sources.push(source);
+            }
+        }
+        self.triangles = triangles;
+        sources
+    }
+
+    // Just the triangles `keep` says yes to (by index), with the vertices they use
+    pub fn select(&self, keep: impl Fn(usize) -> bool) -> IndexedMesh {
+        let mut new_index = vec![usize::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        let triangles = self
+            .triangles
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| keep(*index))
+            .map(|(_, triangle)| {
+                triangle.map(|vertex| {
+                    if new_index[vertex] == usize::MAX {
+                        vertices.push(self.vertices[vertex]);
+                        new_index[vertex] = vertices.len() - 1;
+                    }
+                    new_index[vertex]
+                })
+            })
+            .collect();
+        IndexedMesh {
+            vertices,
+            triangles,
+        }
+    }
+
+    pub fn to_csg(&self) -> CSGMesh {
+        let polygons: Vec<Polygon<()>> = self
+            .triangles
+            .iter()
+            .map(|triangle| {
+                let corners = triangle.map(|vertex| self.vertices[vertex]);
+                let normal = (corners[1] - corners[0])
+                    .cross(corners[2] - corners[0])
+                    .normalize_or_zero();
+                Polygon::new(
+                    corners
+                        .iter()
+                        .map(|corner| {
+                            Vertex::new(
+                                [corner.x, corner.y, corner.z].into(),
+                                [normal.x, normal.y, normal.z].into(),
+                            )
+                        })
+                        .collect(),
+                    None,
+                )
+            })
+            .collect();
+        let mut mesh = CSGMesh::from_polygons(&polygons, None);
+        mesh.renormalize();
+        mesh
+    }
+
+    // Every edge (lower vertex first) with the vertices opposite it in the triangles on either side
+    fn edges(&self) -> HashMap<(usize, usize), Vec<usize>> {
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for [a, b, c] in self.triangles.iter().copied() {
+            for (from, to, opposite) in [(a, b, c), (b, c, a), (c, a, b)] {
+                edges
+                    .entry((from.min(to), from.max(to)))
+                    .or_default()
+                    .push(opposite);
+            }
+        }
+        edges
+    }
+
+    // Neighbours of every vertex, and whether it sits on an edge that isn't shared by exactly two
+    // triangles (a hole, or a seam where two welded parts meet), those don't get moved
+    fn neighbours(&self) -> (Vec<HashSet<usize>>, Vec<bool>) {
+        let mut neighbours = vec![HashSet::new(); self.vertices.len()];
+        let mut on_boundary = vec![false; self.vertices.len()];
+        for ((a, b), opposite) in self.edges() {
+            neighbours[a].insert(b);
+            neighbours[b].insert(a);
+            if opposite.len() != 2 {
+                on_boundary[a] = true;
+                on_boundary[b] = true;
+            }
+        }
+        (neighbours, on_boundary)
+    }
+
+    // One pass of Loop subdivision: every triangle split into four, with the old vertices pulled
+    // towards their neighbours and the new ones weighted towards the triangles either side.
+    // `pinned` vertices (and new ones between two of them) stay exactly where they are.
+    pub fn loop_subdivide(&self, pinned: impl Fn(DVec3) -> bool) -> IndexedMesh {
+        let (neighbours, on_boundary) = self.neighbours();
+        let mut vertices: Vec<DVec3> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex)| {
+                let count = neighbours[i].len();
+                if on_boundary[i] || count < 3 || pinned(*vertex) {
+                    return *vertex;
+                }
+                let beta = if count == 3 {
+                    3.0 / 16.0
+                } else {
+                    3.0 / (8.0 * count as f64)
+                };
+                let around: DVec3 = neighbours[i].iter().map(|j| self.vertices[*j]).sum();
+                *vertex * (1.0 - count as f64 * beta) + around * beta
+            })
+            .collect();
+
+        let mut edge_vertices = HashMap::new();
+        for ((a, b), opposite) in self.edges() {
+            let (start, end) = (self.vertices[a], self.vertices[b]);
+            let edge_vertex = match opposite.as_slice() {
+                [c, d] if !(pinned(start) && pinned(end)) => {
+                    (start + end) * 3.0 / 8.0 + (self.vertices[*c] + self.vertices[*d]) / 8.0
+                }
+                _ => (start + end) / 2.0,
+            };
+            vertices.push(edge_vertex);
+            edge_vertices.insert((a, b), vertices.len() - 1);
+        }
+
+        let edge_vertex = |a: usize, b: usize| edge_vertices[&(a.min(b), a.max(b))];
+        let triangles = self
+            .triangles
+            .iter()
+            .flat_map(|&[a, b, c]| {
+                let (ab, bc, ca) = (edge_vertex(a, b), edge_vertex(b, c), edge_vertex(c, a));
+                [[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]
+            })
+            .collect();
+        IndexedMesh {
+            vertices,
+            triangles,
+        }
+    }
+
+    // Taubin smoothing: Laplacian smoothing that alternates shrinking and growing,
+    // so the surface gets smoother without the whole thing shrinking away.
+    // `pinned` vertices don't move (checked on where they started).
+    pub fn taubin_smooth(&mut self, iterations: usize, pinned: impl Fn(DVec3) -> bool) {
+        let (neighbours, on_boundary) = self.neighbours();
+        let fixed: Vec<bool> = self
+            .vertices
+            .iter()
+            .zip(on_boundary)
+            .map(|(vertex, on_boundary)| on_boundary || pinned(*vertex))
+            .collect();
+        for _ in 0..iterations {
+            for factor in [TAUBIN_LAMBDA, TAUBIN_MU] {
+                self.vertices = self
+                    .vertices
+                    .iter()
+                    .enumerate()
+                    .map(|(i, vertex)| {
+                        if fixed[i] || neighbours[i].is_empty() {
+                            return *vertex;
+                        }
+                        let average = neighbours[i]
+                            .iter()
+                            .map(|j| self.vertices[*j])
+                            .sum::<DVec3>()
+                            / neighbours[i].len() as f64;
+                        *vertex + (average - *vertex) * factor
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    // Normal for each corner of each triangle: the average of the triangles around that vertex that
+    // face within `smoothing_angle` degrees of this one, so creases sharper than that stay sharp
+    // (0 is flat shading)
+    pub fn corner_normals(&self, smoothing_angle: f64) -> Vec<[DVec3; 3]> {
+        // area weighted, bigger triangles count for more
+        let face_normals: Vec<DVec3> = self
+            .triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                (self.vertices[b] - self.vertices[a]).cross(self.vertices[c] - self.vertices[a])
+            })
+            .collect();
+        let mut vertex_faces = vec![Vec::new(); self.vertices.len()];
+        for (face, triangle) in self.triangles.iter().enumerate() {
+            for vertex in triangle {
+                vertex_faces[*vertex].push(face);
+            }
+        }
+
+        let min_cos = smoothing_angle.to_radians().cos();
+        self.triangles
+            .iter()
+            .enumerate()
+            .map(|(face, triangle)| {
+                let normal = face_normals[face].normalize_or_zero();
+                triangle.map(|vertex| {
+                    if smoothing_angle <= 0.0 {
+                        return normal;
+                    }
+                    vertex_faces[vertex]
+                        .iter()
+                        .map(|other| face_normals[*other])
+                        .filter(|other| other.normalize_or_zero().dot(normal) >= min_cos)
+                        .sum::<DVec3>()
+                        .try_normalize()
+                        .unwrap_or(normal)
+                })
+            })
+            .collect()
+    }
+}
+
+// Error of moving a vertex off the planes of its original triangles,
+// summed up as v.A.v + 2 b.v + c (Garland and Heckbert's quadrics)
+#[derive(Clone, Copy)]
+struct Quadric {
+    a: DMat3,
+    b: DVec3,
+    c: f64,
+}
+
+impl Quadric {
+    fn from_plane(normal: DVec3, point: DVec3) -> Self {
+        let d = -normal.dot(point);
+        Quadric {
+            a: DMat3::from_cols(normal * normal.x, normal * normal.y, normal * normal.z),
+            b: normal * d,
+            c: d * d,
+        }
+    }
+
+    fn add(self, other: Quadric) -> Quadric {
+        Quadric {
+            a: self.a + other.a,
+            b: self.b + other.b,
+            c: self.c + other.c,
+        }
+    }
+
+    fn error(&self, point: DVec3) -> f64 {
+        point.dot(self.a * point) + 2.0 * self.b.dot(point) + self.c
+    }
+
+    // Point with the least error, or the best of the fallbacks when that's not well defined (flat areas)
+    fn best_point(&self, fallbacks: &[DVec3]) -> (DVec3, f64) {
+        let mut candidates = fallbacks.to_vec();
+        if self.a.determinant().abs() > 1e-9 {
+            candidates.push(self.a.inverse() * -self.b);
+        }
+        candidates
+            .into_iter()
+            .map(|point| (point, self.error(point)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .unwrap_or((DVec3::ZERO, f64::MAX))
+    }
+}
+
+// Candidate edge collapse in the queue, the cheapest comes out first.
+// The stamps say which version of the two vertices the cost was worked out for.
+struct Collapse {
+    cost: f64,
+    target: DVec3,
+    vertices: [usize; 2],
+    stamps: [u32; 2],
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost.total_cmp(&other.cost).is_eq()
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    // backwards, BinaryHeap pops the biggest
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl IndexedMesh {
+    // Quadric edge collapse decimation: keep collapsing whichever edge changes the shape the least
+    // until there are only `target_triangles` left (or nothing more can go without folding
+    // triangles over or pinching the surface)
+    pub fn decimate(&self, target_triangles: usize) -> IndexedMesh {
+        self.decimate_tracked(target_triangles).0
+    }
+
+    // `decimate`, also giving back which of the original triangles each one left is
+    pub fn decimate_tracked(&self, target_triangles: usize) -> (IndexedMesh, Vec<usize>) {
+        let mut positions = self.vertices.clone();
+        let mut triangles = self.triangles.clone();
+        let mut triangle_alive = vec![true; triangles.len()];
+        let mut alive_count = triangles.len();
+        let mut vertex_removed = vec![false; positions.len()];
+        let mut stamps = vec![0u32; positions.len()];
+        let mut vertex_triangles = vec![Vec::new(); positions.len()];
+        let mut quadrics = vec![
+            Quadric {
+                a: DMat3::ZERO,
+                b: DVec3::ZERO,
+                c: 0.0
+            };
+            positions.len()
+        ];
+        for (index, triangle) in triangles.iter().enumerate() {
+            // flat ones still follow the collapses, they just don't have a plane to add
+            for vertex in triangle {
+                vertex_triangles[*vertex].push(index);
+            }
+            let [a, b, c] = triangle.map(|vertex| positions[vertex]);
+            if let Some(normal) = (b - a).cross(c - a).try_normalize() {
+                for vertex in triangle {
+                    quadrics[*vertex] = quadrics[*vertex].add(Quadric::from_plane(normal, a));
+                }
+            }
+        }
+
+        let collapse =
+            |positions: &[DVec3], quadrics: &[Quadric], stamps: &[u32], a: usize, b: usize| {
+                let (target, cost) = quadrics[a].add(quadrics[b]).best_point(&[
+                    positions[a],
+                    positions[b],
+                    (positions[a] + positions[b]) / 2.0,
+                ]);
+                Collapse {
+                    cost,
+                    target,
+                    vertices: [a, b],
+                    stamps: [stamps[a], stamps[b]],
+                }
+            };
+        let mut queue: BinaryHeap<Collapse> = self
+            .edges()
+            .into_keys()
+            .map(|(a, b)| collapse(&positions, &quadrics, &stamps, a, b))
+            .collect();
+
+        while alive_count > target_triangles {
+            let Some(candidate) = queue.pop() else {
+                break;
+            };
+            let [keep, remove] = candidate.vertices;
+            if vertex_removed[keep]
+                || vertex_removed[remove]
+                || stamps[keep] != candidate.stamps[0]
+                || stamps[remove] != candidate.stamps[1]
+            {
+                continue;
+            }
+
+            let keep_triangles = live_triangles(&vertex_triangles, &triangle_alive, keep);
+            let remove_triangles = live_triangles(&vertex_triangles, &triangle_alive, remove);
+            let (shared, moved): (Vec<usize>, Vec<usize>) = keep_triangles
+                .iter()
+                .chain(remove_triangles.iter())
+                .copied()
+                .collect::<HashSet<usize>>()
+                .into_iter()
+                .partition(|triangle| {
+                    triangles[*triangle].contains(&keep) && triangles[*triangle].contains(&remove)
+                });
+
+            // only as many common neighbours as triangles on the edge, otherwise the surface pinches
+            let common = triangle_neighbours(&triangles, &keep_triangles, keep)
+                .intersection(&triangle_neighbours(&triangles, &remove_triangles, remove))
+                .count();
+            if common != shared.len() {
+                continue;
+            }
+
+            // no triangle should flip over (or squash flat) when its corner moves
+            let folds = moved.iter().any(|triangle| {
+                let corners = triangles[*triangle].map(|vertex| positions[vertex]);
+                let moved_corners = triangles[*triangle].map(|vertex| {
+                    if vertex == keep || vertex == remove {
+                        candidate.target
+                    } else {
+                        positions[vertex]
+                    }
+                });
+                let before = (corners[1] - corners[0]).cross(corners[2] - corners[0]);
+                let after = (moved_corners[1] - moved_corners[0])
+                    .cross(moved_corners[2] - moved_corners[0]);
+                before.normalize_or_zero().dot(after.normalize_or_zero()) < 0.2
+            });
+            if folds {
+                continue;
+            }
+
+            positions[keep] = candidate.target;
+            quadrics[keep] = quadrics[keep].add(quadrics[remove]);
+            vertex_removed[remove] = true;
+            stamps[keep] += 1;
+            for triangle in shared {
+                triangle_alive[triangle] = false;
+                alive_count -= 1;
+            }
+            for triangle in remove_triangles {
+                if triangle_alive[triangle] {
+                    for vertex in triangles[triangle].iter_mut() {
+                        if *vertex == remove {
+                            *vertex = keep;
+                        }
+                    }
+                    vertex_triangles[keep].push(triangle);
+                }
+            }
+            for neighbour in triangle_neighbours(
+                &triangles,
+                &live_triangles(&vertex_triangles, &triangle_alive, keep),
+                keep,
+            ) {
+                queue.push(collapse(&positions, &quadrics, &stamps, keep, neighbour));
+            }
+        }
+
+        // pack what's left
+        let kept: Vec<usize> = (0..triangles.len())
+            .filter(|triangle| triangle_alive[*triangle])
+            .collect();
+        let collapsed = IndexedMesh {
+            vertices: positions,
+            triangles,
+        };
+        (collapsed.select(|triangle| triangle_alive[triangle]), kept)
+    }
+}
+
+// Triangles around a vertex that haven't been collapsed away
+fn live_triangles(
+    vertex_triangles: &[Vec<usize>],
+    triangle_alive: &[bool],
+    vertex: usize,
+) -> Vec<usize> {
+    vertex_triangles[vertex]
+        .iter()
+        .copied()
+        .filter(|triangle| triangle_alive[*triangle])
+        .collect()
+}
+
+// Every other vertex of some triangles around `vertex`
+fn triangle_neighbours(
+    triangles: &[[usize; 3]],
+    around: &[usize],
+    vertex: usize,
+) -> HashSet<usize> {
+    around
+        .iter()
+        .flat_map(|triangle| triangles[*triangle])
+        .filter(|other| *other != vertex)
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // Corners `radius` out along each axis, a triangle in every octant facing out
+    // (the slices and papercraft tests cut and unfold it too)
+    pub fn octahedron(radius: f64) -> IndexedMesh {
+        let vertices = vec![
+            DVec3::X * radius,
+            -DVec3::X * radius,
+            DVec3::Y * radius,
+            -DVec3::Y * radius,
+            DVec3::Z * radius,
+            -DVec3::Z * radius,
+        ];
+        let mut triangles = Vec::new();
+        for x in 0..2 {
+            for y in 0..2 {
+                for z in 0..2 {
+                    // an odd number of negative axes turns the winding inside out
+                    if (x + y + z) % 2 == 0 {
+                        triangles.push([x, 2 + y, 4 + z]);
+                    } else {
+                        triangles.push([x, 4 + z, 2 + y]);
+                    }
+                }
+            }
+        }
+        IndexedMesh {
+            vertices,
+            triangles,
+        }
+    }
+
+    // Every edge with exactly two triangles on it
+    fn is_closed(mesh: &IndexedMesh) -> bool {
+        mesh.edges().values().all(|opposite| opposite.len() == 2)
+    }
+
+    // V - E + F, 2 for anything that's a closed sphere
+    fn euler_characteristic(mesh: &IndexedMesh) -> i64 {
+        let used: HashSet<usize> = mesh.triangles.iter().flatten().copied().collect();
+        used.len() as i64 - mesh.edges().len() as i64 + mesh.triangles.len() as i64
+    }
+
+    #[test]
+    fn closed_mesh_stays_closed() {
+        let mesh = octahedron(10.0);
+        assert!(is_closed(&mesh));
+        assert_eq!(euler_characteristic(&mesh), 2);
+
+        let mut subdivided = mesh.loop_subdivide(|_| false).loop_subdivide(|_| false);
+        assert_eq!(subdivided.triangles.len(), 8 * 16);
+        assert!(is_closed(&subdivided));
+        assert_eq!(euler_characteristic(&subdivided), 2);
+
+        subdivided.taubin_smooth(5, |_| false);
+        assert!(is_closed(&subdivided));
+        assert_eq!(euler_characteristic(&subdivided), 2);
+
+        let (decimated, kept) = subdivided.decimate_tracked(32);
+        assert!(decimated.triangles.len() < subdivided.triangles.len());
+        assert_eq!(decimated.triangles.len(), kept.len());
+        assert!(is_closed(&decimated));
+        assert_eq!(euler_characteristic(&decimated), 2);
+    }
+
+    #[test]
+    fn pinned_vertices_stay_put() {
+        let top = DVec3::Z * 10.0;
+        let mut mesh = octahedron(10.0).loop_subdivide(|vertex| vertex == top);
+        mesh.taubin_smooth(5, |vertex| vertex == top);
+        assert!(mesh.vertices.contains(&top));
+    }
+
+    #[test]
+    fn cube_welds_to_eight_vertices() {
+        let cube = CSGMesh::cuboid(10.0, 10.0, 10.0, None);
+        let (mesh, parts) = IndexedMesh::from_csg_parts(&[&cube]);
+        assert_eq!(mesh.vertices.len(), 8);
+        assert_eq!(mesh.triangles.len(), 12);
+        assert!(parts.iter().all(|part| *part == 0));
+        assert!(is_closed(&mesh));
+        assert_eq!(euler_characteristic(&mesh), 2);
+    }
+}
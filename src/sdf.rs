@@ -0,0 +1,473 @@
+use bevy::math::{DQuat, DVec3};
+use csgrs::mesh::{polygon::Polygon, vertex::Vertex};
+
+use crate::{bird::CSGMesh, stats::mesh_triangles};
+
+// Bird primitives as signed distance functions (negative inside, in mm),
+// an alternative to building everything out of csgrs hulls and booleans
+#[derive(Clone)]
+pub enum Sdf {
+    Sphere {
+        center: DVec3,
+        radius: f64,
+    },
+    Ellipsoid {
+        center: DVec3,
+        radii: DVec3,
+    },
+    // capsule with a different radius at each end (what you get hulling two spheres)
+    RoundCone {
+        from: DVec3,
+        to: DVec3,
+        from_radius: f64,
+        to_radius: f64,
+    },
+    // convex solid as the planes of its faces (outward normal, distance from the origin), for
+    // shapes that are easier to build as csgrs hulls
+    Convex {
+        planes: Vec<(DVec3, f64)>,
+        min: DVec3,
+        max: DVec3,
+    },
+    // inner shape scaled (per axis), then rotated, then moved, like chaining the csgrs transforms
+    Transformed {
+        inner: Box<Sdf>,
+        scale: DVec3,
+        rotation: DQuat,
+        translation: DVec3,
+    },
+    // everything joined up, with fillets `radius` wide where the parts meet (0 is a plain union)
+    SmoothUnion {
+        parts: Vec<Sdf>,
+        radius: f64,
+    },
+    // only what's inside all of the parts
+    Intersection {
+        parts: Vec<Sdf>,
+    },
+    // inner shape with the cuts taken out of it
+    Difference {
+        inner: Box<Sdf>,
+        cuts: Vec<Sdf>,
+    },
+    // everything behind the plane (outward normal, distance from the origin), goes on forever
+    // so it's only any use as a cut or in an intersection, like the csgrs cut boxes
+    HalfSpace {
+        normal: DVec3,
+        offset: f64,
+    },
+}
+
+impl Sdf {
+    // A convex csgrs mesh (like a hull) as a distance field, pulled in by `inset` all round
+    pub fn convex(mesh: &CSGMesh, inset: f64) -> Sdf {
+        let triangles = mesh_triangles(mesh);
+        let planes = triangles
+            .iter()
+            .filter_map(|[a, b, c]| {
+                let normal = (*b - *a).cross(*c - *a).try_normalize()?;
+                Some((normal, normal.dot(*a) - inset))
+            })
+            .collect();
+        let (min, max) = triangles.iter().flatten().fold(
+            (DVec3::splat(f64::MAX), DVec3::splat(f64::MIN)),
+            |(min, max), corner| (min.min(*corner), max.max(*corner)),
+        );
+        Sdf::Convex { planes, min, max }
+    }
+
+    pub fn distance(&self, point: DVec3) -> f64 {
+        match self {
+            Sdf::Sphere { center, radius } => (point - *center).length() - radius,
+            // not exact, but close enough near the surface (see iquilezles.org distance functions)
+            Sdf::Ellipsoid { center, radii } => {
+                let local = point - *center;
+                let k0 = (local / *radii).length();
+                let k1 = (local / (*radii * *radii)).length();
+                if k1 == 0.0 {
+                    -radii.min_element()
+                } else {
+                    k0 * (k0 - 1.0) / k1
+                }
+            }
+            Sdf::RoundCone {
+                from,
+                to,
+                from_radius,
+                to_radius,
+            } => round_cone_distance(point, *from, *to, *from_radius, *to_radius),
+            // exact inside and straight out from the faces, a bit short past the edges and corners
+            Sdf::Convex { planes, .. } => planes
+                .iter()
+                .map(|(normal, offset)| normal.dot(point) - offset)
+                .fold(f64::MIN, f64::max),
+            // non uniform scaling stretches the distances, the smallest scale keeps it a lower bound
+            Sdf::Transformed {
+                inner,
+                scale,
+                rotation,
+                translation,
+            } => {
+                let local = rotation.inverse() * (point - *translation) / *scale;
+                inner.distance(local) * scale.min_element()
+            }
+            Sdf::SmoothUnion { parts, radius } => parts
+                .iter()
+                .map(|part| part.distance(point))
+                .reduce(|a, b| smooth_min(a, b, *radius))
+                .unwrap_or(f64::MAX),
+            Sdf::Intersection { parts } => parts
+                .iter()
+                .map(|part| part.distance(point))
+                .fold(f64::MIN, f64::max),
+            Sdf::Difference { inner, cuts } => cuts
+                .iter()
+                .map(|cut| -cut.distance(point))
+                .fold(inner.distance(point), f64::max),
+            Sdf::HalfSpace { normal, offset } => normal.dot(point) - offset,
+        }
+    }
+
+    // Box the shape fits in (a bit generous for the transformed ones)
+    pub fn bounds(&self) -> (DVec3, DVec3) {
+        match self {
+            Sdf::Sphere { center, radius } => (*center - *radius, *center + *radius),
+            Sdf::Ellipsoid { center, radii } => (*center - *radii, *center + *radii),
+            Sdf::RoundCone {
+                from,
+                to,
+                from_radius,
+                to_radius,
+            } => (
+                (*from - *from_radius).min(*to - *to_radius),
+                (*from + *from_radius).max(*to + *to_radius),
+            ),
+            Sdf::Convex { min, max, .. } => (*min, *max),
+            Sdf::Transformed {
+                inner,
+                scale,
+                rotation,
+                translation,
+            } => {
+                let (inner_min, inner_max) = inner.bounds();
+                (0..8)
+                    .map(|corner| {
+                        let local = DVec3::new(
+                            if corner & 1 == 0 {
+                                inner_min.x
+                            } else {
+                                inner_max.x
+                            },
+                            if corner & 2 == 0 {
+                                inner_min.y
+                            } else {
+                                inner_max.y
+                            },
+                            if corner & 4 == 0 {
+                                inner_min.z
+                            } else {
+                                inner_max.z
+                            },
+                        );
+                        *rotation * (local * *scale) + *translation
+                    })
+                    .fold(
+                        (DVec3::splat(f64::MAX), DVec3::splat(f64::MIN)),
+                        |(min, max), corner| (min.min(corner), max.max(corner)),
+                    )
+            }
+            // the blend can bulge out past the parts by a quarter of the radius
+            Sdf::SmoothUnion { parts, radius } => {
+                let (min, max) = parts.iter().map(|part| part.bounds()).fold(
+                    (DVec3::splat(f64::MAX), DVec3::splat(f64::MIN)),
+                    |(min, max), (part_min, part_max)| (min.min(part_min), max.max(part_max)),
+                );
+                (min - radius / 4.0, max + radius / 4.0)
+            }
+            Sdf::Intersection { parts } => parts.iter().map(|part| part.bounds()).fold(
+                (DVec3::splat(f64::MIN), DVec3::splat(f64::MAX)),
+                |(min, max), (part_min, part_max)| (min.max(part_min), max.min(part_max)),
+            ),
+            // cutting bits out never makes it any bigger
+            Sdf::Difference { inner, .. } => inner.bounds(),
+            Sdf::HalfSpace { .. } => (DVec3::splat(f64::MIN), DVec3::splat(f64::MAX)),
+        }
+    }
+
+    // Turn the surface into a closed mesh: sample the distances on a grid (`cells` along the longest
+    // side), then marching tetrahedra, which is marching cubes with every cube cut into 6 tetrahedra.
+    // No lookup tables, and since neighbouring cubes cut their faces the same way and every edge
+    // point gets worked out from the same two samples in the same order, the triangles all share
+    // their edges exactly, no cracks.
+    pub fn polygonize(&self, cells: usize) -> CSGMesh {
+        let (min, max) = self.bounds();
+        let cell_size = (max - min).max_element() / cells as f64;
+        // a couple of cells of outside all around, so the surface closes
+        let min = min - cell_size * 2.0;
+        let max = max + cell_size * 2.0;
+        let [nx, ny, nz] = ((max - min) / cell_size)
+            .ceil()
+            .as_uvec3()
+            .to_array()
+            .map(|n| n as usize + 1);
+
+        let grid_point = |x: usize, y: usize, z: usize| {
+            min + DVec3::new(x as f64, y as f64, z as f64) * cell_size
+        };
+        let index = |x: usize, y: usize, z: usize| x + nx * (y + ny * z);
+        let mut points = Vec::with_capacity(nx * ny * nz);
+        let mut values = Vec::with_capacity(nx * ny * nz);
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    let point = grid_point(x, y, z);
+                    points.push(point);
+                    values.push(self.distance(point));
+                }
+            }
+        }
+
+        // where the surface crosses between two samples, always worked out from the lower index
+        let edge_point = |a: usize, b: usize| {
+            let (a, b) = if a < b { (a, b) } else { (b, a) };
+            let t = (values[a] / (values[a] - values[b])).clamp(EDGE_MARGIN, 1.0 - EDGE_MARGIN);
+            points[a] + (points[b] - points[a]) * t
+        };
+
+        let mut polygons: Vec<Polygon<()>> = Vec::new();
+        // outward is wherever the outside samples are
+        let mut add_face = |corners: &[DVec3], outward: DVec3| {
+            let normal = (corners[1] - corners[0]).cross(corners[2] - corners[0]);
+            if normal.length_squared() < 1e-18 {
+                return;
+            }
+            let mut corners = corners.to_vec();
+            let mut normal = normal.normalize();
+            if normal.dot(outward) < 0.0 {
+                corners.reverse();
+                normal = -normal;
+            }
+            polygons.push(Polygon::new(
+                corners
+                    .iter()
+                    .map(|corner| {
+                        Vertex::new(
+                            [corner.x, corner.y, corner.z].into(),
+                            [normal.x, normal.y, normal.z].into(),
+                        )
+                    })
+                    .collect(),
+                None,
+            ));
+        };
+
+        for z in 0..nz - 1 {
+            for y in 0..ny - 1 {
+                for x in 0..nx - 1 {
+                    let cube: [usize; 8] = std::array::from_fn(|corner| {
+                        index(
+                            x + (corner & 1),
+                            y + ((corner >> 1) & 1),
+                            z + ((corner >> 2) & 1),
+                        )
+                    });
+                    for tetrahedron in CUBE_TETRAHEDRA {
+                        let corners = tetrahedron.map(|corner| cube[corner]);
+                        let (inside, outside): (Vec<usize>, Vec<usize>) =
+                            corners.iter().partition(|corner| values[**corner] < 0.0);
+                        if inside.is_empty() || outside.is_empty() {
+                            continue;
+                        }
+                        let middle = |corners: &[usize]| {
+                            corners.iter().map(|corner| points[*corner]).sum::<DVec3>()
+                                / corners.len() as f64
+                        };
+                        let outward = middle(&outside) - middle(&inside);
+                        match (inside.as_slice(), outside.as_slice()) {
+                            // one corner cut off, a triangle
+                            ([single], others) | (others, [single]) => {
+                                let triangle: Vec<DVec3> = others
+                                    .iter()
+                                    .map(|other| edge_point(*single, *other))
+                                    .collect();
+                                add_face(&triangle, outward);
+                            }
+                            // cut across the middle, a quad (as two triangles, it might not be flat)
+                            ([a, b], [c, d]) => {
+                                let quad = [
+                                    edge_point(*a, *c),
+                                    edge_point(*a, *d),
+                                    edge_point(*b, *d),
+                                    edge_point(*b, *c),
+                                ];
+                                add_face(&[quad[0], quad[1], quad[2]], outward);
+                                add_face(&[quad[0], quad[2], quad[3]], outward);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut mesh = CSGMesh::from_polygons(&polygons, None);
+        mesh.renormalize();
+        mesh
+    }
+}
+
+// Edge points stay at least this far (as a fraction of the edge) from the samples, otherwise where
+// the surface runs right past a sample the points on its edges all bunch up on it and get welded
+// together into a pinch
+const EDGE_MARGIN: f64 = 0.01;
+
+// Cube corners are numbered by bits (x is 1, y is 2, z is 4), and every tetrahedron runs
+// from corner 0 to corner 7 along a different path over the edges
+const CUBE_TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 3, 7],
+    [0, 1, 5, 7],
+    [0, 2, 3, 7],
+    [0, 2, 6, 7],
+    [0, 4, 5, 7],
+    [0, 4, 6, 7],
+];
+
+// Polynomial smooth min, `radius` is how far apart the two distances can be and still blend
+pub fn smooth_min(a: f64, b: f64, radius: f64) -> f64 {
+    if radius <= 0.0 {
+        return a.min(b);
+    }
+    let h = (radius - (a - b).abs()).max(0.0) / radius;
+    a.min(b) - h * h * radius / 4.0
+}
+
+// Exact round cone distance (from iquilezles.org), falls back to the bigger sphere when one
+// end swallows the other
+fn round_cone_distance(
+    point: DVec3,
+    from: DVec3,
+    to: DVec3,
+    from_radius: f64,
+    to_radius: f64,
+) -> f64 {
+    let along = to - from;
+    let length_squared = along.length_squared();
+    let radius_change = from_radius - to_radius;
+    let a2 = length_squared - radius_change * radius_change;
+    if a2 <= 0.0 {
+        return if from_radius > to_radius {
+            (point - from).length() - from_radius
+        } else {
+            (point - to).length() - to_radius
+        };
+    }
+    let inverse_length_squared = 1.0 / length_squared;
+
+    let relative = point - from;
+    let y = relative.dot(along);
+    let z = y - length_squared;
+    let x2 = (relative * length_squared - along * y).length_squared();
+    let y2 = y * y * length_squared;
+    let z2 = z * z * length_squared;
+
+    let k = radius_change.signum() * radius_change * radius_change * x2;
+    if z.signum() * a2 * z2 > k {
+        return (x2 + z2).sqrt() * inverse_length_squared - to_radius;
+    }
+    if y.signum() * a2 * y2 < k {
+        return (x2 + y2).sqrt() * inverse_length_squared - from_radius;
+    }
+    ((x2 * a2 * inverse_length_squared).sqrt() + y * radius_change) * inverse_length_squared
+        - from_radius
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::remesh::IndexedMesh;
+
+    // Every edge with exactly two triangles on it, once the corners are welded
+    fn is_closed(mesh: &CSGMesh) -> bool {
+        let indexed = IndexedMesh::from_csg(mesh);
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for triangle in indexed.triangles.iter() {
+            for corner in 0..3 {
+                let (a, b) = (triangle[corner], triangle[(corner + 1) % 3]);
+                *edges.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+        !edges.is_empty() && edges.values().all(|count| *count == 2)
+    }
+
+    fn volume(mesh: &CSGMesh) -> f64 {
+        mesh_triangles(mesh)
+            .iter()
+            .map(|[a, b, c]| a.dot(b.cross(*c)) / 6.0)
+            .sum()
+    }
+
+    #[test]
+    fn sphere_is_watertight() {
+        let radius = 10.0;
+        let mesh = Sdf::Sphere {
+            center: DVec3::new(5.0, -3.0, 2.0),
+            radius,
+        }
+        .polygonize(32);
+        assert!(is_closed(&mesh));
+        let expected = 4.0 / 3.0 * std::f64::consts::PI * radius.powi(3);
+        assert!((volume(&mesh) - expected).abs() < expected * 0.03);
+    }
+
+    #[test]
+    fn half_space_cuts_in_half() {
+        let radius = 10.0;
+        let mesh = Sdf::Difference {
+            inner: Box::new(Sdf::Sphere {
+                center: DVec3::ZERO,
+                radius,
+            }),
+            cuts: vec![Sdf::HalfSpace {
+                normal: DVec3::Z,
+                offset: 0.0,
+            }],
+        }
+        .polygonize(32);
+        assert!(is_closed(&mesh));
+        let expected = 2.0 / 3.0 * std::f64::consts::PI * radius.powi(3);
+        assert!((volume(&mesh) - expected).abs() < expected * 0.03);
+        assert!(
+            mesh_triangles(&mesh)
+                .iter()
+                .flatten()
+                .all(|corner| corner.z > -1e-6)
+        );
+    }
+
+    #[test]
+    fn smooth_union_only_ever_adds() {
+        assert_eq!(smooth_min(1.0, 2.0, 0.0), 1.0);
+        assert!(smooth_min(1.0, 1.5, 2.0) < 1.0);
+        // far enough apart, no blending
+        assert_eq!(smooth_min(1.0, 5.0, 2.0), 1.0);
+
+        let spheres = |radius| Sdf::SmoothUnion {
+            parts: vec![
+                Sdf::Sphere {
+                    center: DVec3::ZERO,
+                    radius: 5.0,
+                },
+                Sdf::Sphere {
+                    center: DVec3::X * 8.0,
+                    radius: 5.0,
+                },
+            ],
+            radius,
+        };
+        let blended = spheres(4.0).polygonize(32);
+        assert!(is_closed(&blended));
+        assert!(volume(&blended) > volume(&spheres(0.0).polygonize(32)));
+    }
+}
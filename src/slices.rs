@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+
+use bevy::{log::warn, math::DVec3};
+use geo::{
+    Area, BooleanOps, BoundingRect, Coord, InteriorPoint, LineString, MultiPolygon, Polygon, Rect,
+};
+
+use crate::{
+    bird::{BirdGenInputs, BirdParts, OutlineView, SliceAxis},
+    outline::{project, shelf_pack, svg_document, svg_path},
+    remesh::IndexedMesh,
+    stats::MeshStats,
+};
+
+// Bits smaller than this (in mm^2) aren't worth cutting
+const MIN_PIECE_AREA: f64 = 1.0;
+// Section ends closer than this (in mm) count as the same point
+const SECTION_TOLERANCE: f64 = 1e-3;
+// Gap between pieces, and around the edge of the sheet
+const SHEET_GAP: f64 = 3.0;
+const LABEL_SIZE: f64 = 4.0;
+
+// One piece to cut, with the number engraved on it
+struct Piece {
+    label: String,
+    shape: MultiPolygon<f64>,
+}
+
+// Which way the cross sections look, cutting across each axis
+fn section_view(axis: usize) -> OutlineView {
+    match axis {
+        0 => OutlineView::Front,
+        1 => OutlineView::Side,
+        _ => OutlineView::Top,
+    }
+}
+
+// Cross section of a closed mesh where `axis` is `offset`. Every triangle that crosses the plane
+// gives one little segment of the section, turned to run the same way round as the triangle
+// faces, so each segment starts where the one before it ends. Ends get matched up by where they
+// are (to within `SECTION_TOLERANCE`) rather than by which edge they came from, so a T-junction
+// (one long edge against two short ones) still joins up. Anything that doesn't close up gets
+// left out rather than drawn as a sliver.
+fn mesh_section(mesh: &IndexedMesh, axis: usize, offset: f64) -> MultiPolygon<f64> {
+    // a vertex right on the plane counts as above, so every edge is either crossed or not
+    let above = |vertex: usize| mesh.vertices[vertex][axis] >= offset;
+    // worked out from the lower numbered end, so both triangles on an edge get the same point
+    let edge_point = |(a, b): (usize, usize)| {
+        let (from, to) = (mesh.vertices[a.min(b)], mesh.vertices[a.max(b)]);
+        let t = (offset - from[axis]) / (to[axis] - from[axis]);
+        from + (to - from) * t
+    };
+    let key = |point: Coord<f64>| {
+        [
+            (point.x / SECTION_TOLERANCE).round() as i64,
+            (point.y / SECTION_TOLERANCE).round() as i64,
+        ]
+    };
+    let mut plane_normal = DVec3::ZERO;
+    plane_normal[axis] = 1.0;
+
+    let mut segments: Vec<(Coord<f64>, Coord<f64>)> = Vec::new();
+    for triangle in mesh.triangles.iter() {
+        let crossed: Vec<DVec3> = (0..3)
+            .map(|corner| (triangle[corner], triangle[(corner + 1) % 3]))
+            .filter(|(a, b)| above(*a) != above(*b))
+            .map(edge_point)
+            .collect();
+        let [start, end] = crossed[..] else {
+            continue;
+        };
+        // round the outside of the solid the same way every time
+        let [a, b, c] = triangle.map(|vertex| mesh.vertices[vertex]);
+        let along = plane_normal.cross((b - a).cross(c - a));
+        let (start, end) = if (end - start).dot(along) < 0.0 {
+            (end, start)
+        } else {
+            (start, end)
+        };
+        let (start, end) = (
+            project(start, section_view(axis)),
+            project(end, section_view(axis)),
+        );
+        // a corner sitting right on the plane gives a segment with no length
+        if key(start) != key(end) {
+            segments.push((start, end));
+        }
+    }
+
+    let mut starting_at: HashMap<[i64; 2], Vec<usize>> = HashMap::new();
+    for (index, (start, _)) in segments.iter().enumerate() {
+        starting_at.entry(key(*start)).or_default().push(index);
+    }
+    let mut used = vec![false; segments.len()];
+    let mut loops: Vec<Polygon<f64>> = Vec::new();
+    for first in 0..segments.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut last = first;
+        let mut current = Some(first);
+        while let Some(segment) = current {
+            used[segment] = true;
+            ring.push(segments[segment].0);
+            last = segment;
+            current = starting_at
+                .get(&key(segments[segment].1))
+                .and_then(|next| next.iter().find(|next| !used[**next]))
+                .copied();
+        }
+        let closed = key(segments[last].1) == key(segments[first].0);
+        if closed && ring.len() >= 3 {
+            loops.push(Polygon::new(LineString::from(ring), vec![]));
+        }
+    }
+    // loops inside loops are holes, even-odd sorts that out
+    loops
+        .iter()
+        .fold(MultiPolygon::new(vec![]), |section, ring| section.xor(ring))
+}
+
+// Cross section of the whole bird (all the parts together)
+pub fn bird_section(meshes: &[IndexedMesh], axis: usize, offset: f64) -> MultiPolygon<f64> {
+    meshes
+        .iter()
+        .map(|mesh| mesh_section(mesh, axis, offset))
+        .fold(MultiPolygon::new(vec![]), |section, part| {
+            section.union(&part)
+        })
+}
+
+fn axis_index(axis: SliceAxis) -> usize {
+    match axis {
+        SliceAxis::Length => 0,
+        SliceAxis::Width => 1,
+        SliceAxis::Height => 2,
+    }
+}
+
+// A stack of slices one sheet thick, cut through the middle of each layer, numbered from one end
+fn stack_pieces(meshes: &[IndexedMesh], stats: &MeshStats, input: &BirdGenInputs) -> Vec<Piece> {
+    let axis = axis_index(input.slice_axis);
+    let thickness = input.sheet_thickness as f64;
+    let start = stats.bounding_box_min[axis];
+    let layers = ((stats.bounding_box_max[axis] - start) / thickness).ceil() as usize;
+    (0..layers)
+        .map(|layer| bird_section(meshes, axis, start + (layer as f64 + 0.5) * thickness))
+        .filter(|section| section.unsigned_area() > MIN_PIECE_AREA)
+        .enumerate()
+        .map(|(index, shape)| Piece {
+            label: (index + 1).to_string(),
+            shape,
+        })
+        .collect()
+}
+
+// Offsets for `count` slices `spacing` apart, centred on the bird
+fn centred_offsets(min: f64, max: f64, spacing: f64) -> Vec<f64> {
+    let count = ((max - min) / spacing).floor().max(1.0) as usize;
+    let middle = (min + max) / 2.0;
+    (0..count)
+        .map(|index| middle + (index as f64 - (count - 1) as f64 / 2.0) * spacing)
+        .collect()
+}
+
+// Height range a section covers inside a thin upright strip at `across`
+fn strip_heights(section: &MultiPolygon<f64>, across: f64, width: f64) -> Option<(f64, f64)> {
+    let bounds = section.bounding_rect()?;
+    let strip = slot(across, width, bounds.min().y - 1.0, bounds.max().y + 1.0);
+    let overlap = section.intersection(&strip).bounding_rect()?;
+    Some((overlap.min().y, overlap.max().y))
+}
+
+// Upright slot `width` wide at `across`, running from `bottom` to `top`
+fn slot(across: f64, width: f64, bottom: f64, top: f64) -> Polygon<f64> {
+    Rect::new(
+        Coord {
+            x: across - width / 2.0,
+            y: bottom,
+        },
+        Coord {
+            x: across + width / 2.0,
+            y: top,
+        },
+    )
+    .to_polygon()
+}
+
+// Sliceform: upright slices across the bird (A) and along it (B) that slot into each other.
+// Where two cross, the A slice gets a slot from the top down to halfway and the B slice one from
+// the bottom up to halfway, so they push together into a grid.
+fn sliceform_pieces(
+    meshes: &[IndexedMesh],
+    stats: &MeshStats,
+    input: &BirdGenInputs,
+) -> Vec<Piece> {
+    let spacing = input.slice_spacing as f64;
+    let slot_width = input.sheet_thickness as f64;
+    let (min, max) = (stats.bounding_box_min, stats.bounding_box_max);
+
+    let mut across: Vec<(f64, MultiPolygon<f64>)> = centred_offsets(min.x, max.x, spacing)
+        .into_iter()
+        .map(|x| (x, bird_section(meshes, 0, x)))
+        .filter(|(_, section)| section.unsigned_area() > MIN_PIECE_AREA)
+        .collect();
+    let mut along: Vec<(f64, MultiPolygon<f64>)> = centred_offsets(min.y, max.y, spacing)
+        .into_iter()
+        .map(|y| (y, bird_section(meshes, 1, y)))
+        .filter(|(_, section)| section.unsigned_area() > MIN_PIECE_AREA)
+        .collect();
+
+    let mut across_slots: Vec<Vec<Polygon<f64>>> = vec![Vec::new(); across.len()];
+    let mut along_slots: Vec<Vec<Polygon<f64>>> = vec![Vec::new(); along.len()];
+    for (a, (x, across_section)) in across.iter().enumerate() {
+        for (b, (y, along_section)) in along.iter().enumerate() {
+            // the crossing line is at -y on the across slices (looking beak on) and x on the along ones
+            let Some((across_bottom, across_top)) = strip_heights(across_section, -y, slot_width)
+            else {
+                continue;
+            };
+            let Some((along_bottom, along_top)) = strip_heights(along_section, *x, slot_width)
+            else {
+                continue;
+            };
+            let (bottom, top) = (across_bottom.max(along_bottom), across_top.min(along_top));
+            if bottom >= top {
+                continue;
+            }
+            let middle = (bottom + top) / 2.0;
+            across_slots[a].push(slot(-y, slot_width, middle, across_top + 1.0));
+            along_slots[b].push(slot(*x, slot_width, along_bottom - 1.0, middle));
+        }
+    }
+    for ((_, section), slots) in across.iter_mut().zip(across_slots) {
+        for slot in slots {
+            *section = section.difference(&slot);
+        }
+    }
+    for ((_, section), slots) in along.iter_mut().zip(along_slots) {
+        for slot in slots {
+            *section = section.difference(&slot);
+        }
+    }
+
+    // A1, A2.. from beak to tail, B1, B2.. from left to right
+    across
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, shape))| Piece {
+            label: format!("A{}", index + 1),
+            shape,
+        })
+        .chain(
+            along
+                .into_iter()
+                .enumerate()
+                .map(|(index, (_, shape))| Piece {
+                    label: format!("B{}", index + 1),
+                    shape,
+                }),
+        )
+        .collect()
+}
+
+// Every piece placed on a sheet, wherever the shelf packing put it
+fn layout_sheets(pieces: Vec<Piece>, width: f64, height: f64) -> Vec<Vec<(Piece, Coord<f64>)>> {
+    let sizes: Vec<(f64, f64)> = pieces
+        .iter()
+        .map(|piece| {
+            piece
+                .shape
+                .bounding_rect()
+                .map(|bounds| (bounds.width(), bounds.height()))
+                .unwrap_or_default()
+        })
+        .collect();
+    for (piece, (piece_width, piece_height)) in pieces.iter().zip(sizes.iter()) {
+        if piece_width + SHEET_GAP * 2.0 > width || piece_height + SHEET_GAP * 2.0 > height {
+            warn!("Slice {} is bigger than the sheet", piece.label);
+        }
+    }
+    let mut pieces: Vec<Option<Piece>> = pieces.into_iter().map(Some).collect();
+    shelf_pack(&sizes, width, height, SHEET_GAP)
+        .into_iter()
+        .map(|sheet| {
+            sheet
+                .into_iter()
+                .filter_map(|(index, place)| pieces[index].take().map(|piece| (piece, place)))
+                .collect()
+        })
+        .collect()
+}
+
+// One SVG per sheet: cut lines in red, the numbers to engrave in blue
+fn sheet_svg(sheet: &[(Piece, Coord<f64>)], width: f64, height: f64) -> String {
+    let mut content = String::new();
+    for (piece, place) in sheet {
+        let Some(bounds) = piece.shape.bounding_rect() else {
+            continue;
+        };
+        // SVG has y going down the page
+        let to_page = |coord: Coord<f64>| Coord {
+            x: coord.x - bounds.min().x + place.x,
+            y: bounds.max().y - coord.y + place.y,
+        };
+        for polygon in piece.shape.iter() {
+            content += &format!(
+                "  <path d=\"{}\" fill=\"none\" stroke=\"red\" stroke-width=\"0.1\" />\n",
+                svg_path(polygon, to_page)
+            );
+        }
+        if let Some(label_point) = piece.shape.interior_point() {
+            let label_point = to_page(label_point.0);
+            content += &format!(
+                "  <text x=\"{:.3}\" y=\"{:.3}\" font-family=\"sans-serif\" font-size=\"{}\" fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+                label_point.x, label_point.y, LABEL_SIZE, piece.label
+            );
+        }
+    }
+    svg_document(width, height, &content)
+}
+
+// The bird cut into slices and laid out on numbered sheets, one SVG each
+pub fn bird_slice_sheets(parts: &BirdParts, input: &BirdGenInputs) -> Vec<String> {
+    let meshes: Vec<IndexedMesh> = parts
+        .meshes()
+        .into_iter()
+        .map(IndexedMesh::from_csg)
+        .collect();
+    let stats = MeshStats::from_meshes(&parts.meshes());
+    let pieces = if input.sliceform {
+        sliceform_pieces(&meshes, &stats, input)
+    } else {
+        stack_pieces(&meshes, &stats, input)
+    };
+    let (width, height) = (input.sheet_width as f64, input.sheet_height as f64);
+    layout_sheets(pieces, width, height)
+        .iter()
+        .map(|sheet| sheet_svg(sheet, width, height))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remesh::tests::octahedron;
+
+    #[test]
+    fn octahedron_sections_are_squares() {
+        let mesh = octahedron(10.0);
+        for axis in 0..3 {
+            // through the middle, the section runs corner to corner
+            let middle = mesh_section(&mesh, axis, 0.0);
+            assert_eq!(middle.0.len(), 1);
+            assert!((middle.unsigned_area() - 200.0).abs() < 1e-6);
+            let halfway = mesh_section(&mesh, axis, 5.0);
+            assert_eq!(halfway.0.len(), 1);
+            assert!((halfway.unsigned_area() - 50.0).abs() < 1e-6);
+        }
+        assert!(mesh_section(&mesh, 2, 20.0).0.is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::{ecs::resource::Resource, math::DVec3};
+use serde::Serialize;
+
+use crate::bird::{BirdGenInputs, BirdParts, CSGMesh};
+
+// Rough slicer assumptions for the print estimate (0.4mm nozzle, two perimeters)
+const LINE_WIDTH: f64 = 0.4;
+const WALL_THICKNESS: f64 = 2.0 * LINE_WIDTH;
+// travel moves, acceleration, retractions... all the stuff that isn't extruding
+const PRINT_TIME_OVERHEAD: f64 = 1.3;
+
+// Measurements of the generated csgrs meshes, everything in mm (OpenSCAD style z up)
+#[derive(Clone, Copy, Default)]
+pub struct MeshStats {
+    pub triangle_count: usize,
+    pub volume: f64,       // mm^3
+    pub surface_area: f64, // mm^2
+    pub bounding_box_min: DVec3,
+    pub bounding_box_max: DVec3,
+}
+
+// Stats of the bird currently on screen (updated every time the bird gets rebuilt)
+#[derive(Resource, Clone, Copy, Default)]
+pub struct BirdStats {
+    pub mesh: MeshStats,
+}
+
+#[derive(Clone, Copy)]
+pub struct PrintEstimate {
+    pub filament_grams: f64,
+    pub filament_length: f64, // mm
+    pub print_minutes: f64,
+}
+
+impl MeshStats {
+    // Stats for a bunch of meshes, taken together
+    pub fn from_meshes(meshes: &[&CSGMesh]) -> Self {
+        let mut stats = MeshStats {
+            bounding_box_min: DVec3::splat(f64::MAX),
+            bounding_box_max: DVec3::splat(f64::MIN),
+            ..Default::default()
+        };
+        for [a, b, c] in meshes.iter().flat_map(|mesh| mesh_triangles(mesh)) {
+            stats.triangle_count += 1;
+            // signed volume of the tetrahedron to the origin, adds up to the enclosed volume
+            stats.volume += a.dot(b.cross(c)) / 6.0;
+            stats.surface_area += (b - a).cross(c - a).length() / 2.0;
+            for corner in [a, b, c] {
+                stats.bounding_box_min = stats.bounding_box_min.min(corner);
+                stats.bounding_box_max = stats.bounding_box_max.max(corner);
+            }
+        }
+        if stats.triangle_count == 0 {
+            return MeshStats::default();
+        }
+        stats
+    }
+
+    // Stats for the bird as it prints. The parts overlap (the head sinks into the neck, the
+    // markings sit on top of what they're painted on..) so they get unioned first, otherwise
+    // the volume and everything worked out from it would count the overlaps twice.
+    pub fn from_parts(parts: &BirdParts) -> Self {
+        MeshStats::from_meshes(&[&parts.solid()])
+    }
+
+    pub fn size(&self) -> DVec3 {
+        self.bounding_box_max - self.bounding_box_min
+    }
+}
+
+impl PrintEstimate {
+    // Very rough: solid walls all around, the rest of the volume at the infill ratio
+    pub fn from_mesh_stats(stats: &MeshStats, input: &BirdGenInputs) -> Self {
+        let shell_volume = (stats.surface_area * WALL_THICKNESS).min(stats.volume);
+        let infill_volume = (stats.volume - shell_volume) * input.infill as f64 / 100.0;
+        let extruded_volume = shell_volume + infill_volume;
+
+        let filament_radius = input.filament_diameter.millimeters() / 2.0;
+        let filament_length =
+            extruded_volume / (std::f64::consts::PI * filament_radius * filament_radius);
+        // density is g/cm^3, volume is mm^3
+        let filament_grams = extruded_volume / 1000.0 * input.material_density as f64;
+
+        let path_length = extruded_volume / (LINE_WIDTH * input.layer_height as f64);
+        let print_minutes = path_length / input.print_speed as f64 / 60.0 * PRINT_TIME_OVERHEAD;
+
+        PrintEstimate {
+            filament_grams,
+            filament_length,
+            print_minutes,
+        }
+    }
+}
+
+// Fan out each (planar) polygon into triangles
+pub fn mesh_triangles(mesh: &CSGMesh) -> Vec<[DVec3; 3]> {
+    let mut triangles = Vec::new();
+    for polygon in mesh.polygons.iter() {
+        let points: Vec<DVec3> = polygon
+            .vertices
+            .iter()
+            .map(|vertex| DVec3::new(vertex.pos.x, vertex.pos.y, vertex.pos.z))
+            .collect();
+        for i in 1..points.len().saturating_sub(1) {
+            triangles.push([points[0], points[i], points[i + 1]]);
+        }
+    }
+    triangles
+}
+
+pub fn format_print_time(minutes: f64) -> String {
+    let minutes = minutes.round() as u64;
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    }
+}
+
+#[derive(Serialize)]
+struct BoundingBoxJson {
+    min: [f64; 3],
+    max: [f64; 3],
+    size: [f64; 3],
+}
+
+#[derive(Serialize)]
+struct BirdStatsJson {
+    triangle_count: usize,
+    volume_mm3: f64,
+    surface_area_mm2: f64,
+    bounding_box: BoundingBoxJson,
+    filament_grams: f64,
+    filament_length_m: f64,
+    print_time_minutes: f64,
+}
+
+// What `rusty-bird --stats` prints
+pub fn bird_stats_json(stats: &MeshStats, estimate: &PrintEstimate) -> String {
+    let stats_json = BirdStatsJson {
+        triangle_count: stats.triangle_count,
+        volume_mm3: stats.volume,
+        surface_area_mm2: stats.surface_area,
+        bounding_box: BoundingBoxJson {
+            min: stats.bounding_box_min.to_array(),
+            max: stats.bounding_box_max.to_array(),
+            size: stats.size().to_array(),
+        },
+        filament_grams: estimate.filament_grams,
+        filament_length_m: estimate.filament_length / 1000.0,
+        print_time_minutes: estimate.print_minutes,
+    };
+    serde_json::to_string_pretty(&stats_json).unwrap_or_default()
+}
@@ -1,13 +1,28 @@
 use crate::{
-    BirdState, RebuildBird,
-    bird::{BirdGenInputTypes, BirdGenInputs, get_input_type_string, get_input_value_for_type},
+    BirdState, CurrentBird, RebuildBird,
+    bird::{
+        BeakShape, BirdGenInputTypes, BirdGenInputs, BirdGenOptionTypes, BirdStand, BodyStyle,
+        GeometryBackend, TailStyle, WingPose, cycle_option_value, get_input_type_string,
+        get_input_value_for_type, get_option_type_string, get_option_value_string, part_colour,
+        part_colour_mut,
+    },
+    export::{bird_3mf, bird_glb, bird_stls, meshes_stl},
+    files::{load_design, save_design, save_file},
+    mould::bird_mould,
+    outline::{bird_outline, outline_dxf, outline_svg},
+    papercraft::bird_papercraft_pages,
+    slices::bird_slice_sheets,
+    stats::{BirdStats, PrintEstimate, format_print_time},
 };
 use accesskit::{Node as Accessible, Role};
 use bevy::{
     a11y::AccessibilityNode,
     color::palettes::basic::*,
-    input::mouse::{MouseScrollUnit, MouseWheel},
-    input_focus::tab_navigation::TabGroup,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        mouse::{MouseScrollUnit, MouseWheel},
+    },
+    input_focus::{FocusedInput, InputFocus, tab_navigation::TabGroup},
     picking::hover::{HoverMap, Hovered},
     prelude::*,
     ui::InteractionDisabled,
@@ -33,6 +48,10 @@ impl Plugin for BirdUIPlugin {
                 (
                     send_scroll_events,
                     update_slider_values,
+                    update_option_labels,
+                    update_colour_swatch,
+                    update_name_text_field,
+                    update_stats_text,
                     update_slider_styles,
                     update_slider_styles2,
                     update_button_style,
@@ -52,11 +71,31 @@ struct RegenerateButton;
 #[derive(Component)]
 struct RandomizeButton;
 
+// Any of the smaller buttons under regenerate/randomize (saving, loading, exporting)
+#[derive(Component)]
+struct ActionButton;
+
+#[derive(Component)]
+struct BirdColourSwatch;
+
+#[derive(Component)]
+struct NameTextField;
+
+const NAME_TEXT_MAX_LENGTH: usize = 24;
+
 #[derive(Component)]
 struct BirdInputSlider {
     input_type: BirdGenInputTypes,
 }
 
+#[derive(Component)]
+struct BirdStatsText;
+
+#[derive(Component)]
+struct BirdOptionButton {
+    option_type: BirdGenOptionTypes,
+}
+
 /// UI scrolling event.
 #[derive(EntityEvent, Debug)]
 #[entity_event(propagate, auto_propagate)]
@@ -226,6 +265,23 @@ fn ui_root(asset_server: &AssetServer) -> impl Bundle {
                         200.0,
                         105.0
                     ),
+                    option_button(asset_server, BirdGenOptionTypes::BeakShape),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.beak_curve = v,
+                        BirdGenInputTypes::BeakCurve,
+                        -45.0,
+                        90.0,
+                        20.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.beak_droop = v,
+                        BirdGenInputTypes::BeakDroop,
+                        0.0,
+                        100.0,
+                        40.0
+                    ),
                     separator(),
                     // Head Section
                     section_header(asset_server, "Head"),
@@ -253,6 +309,31 @@ fn ui_root(asset_server: &AssetServer) -> impl Bundle {
                         20.0,
                         10.0
                     ),
+                    option_button(asset_server, BirdGenOptionTypes::EyeStyle),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.eye_spacing = v,
+                        BirdGenInputTypes::EyeSpacing,
+                        0.0,
+                        90.0,
+                        50.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.eye_angle = v,
+                        BirdGenInputTypes::EyeAngle,
+                        -30.0,
+                        90.0,
+                        40.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.eye_bead_size = v,
+                        BirdGenInputTypes::EyeBeadSize,
+                        2.0,
+                        16.0,
+                        6.0
+                    ),
                     slider(
                         asset_server,
                         |inputs, v| inputs.head_lateral_offset = v,
@@ -285,6 +366,46 @@ fn ui_root(asset_server: &AssetServer) -> impl Bundle {
                         45.0,
                         -17.5
                     ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.neck_fillet = v,
+                        BirdGenInputTypes::NeckFillet,
+                        0.0,
+                        20.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.crest_height = v,
+                        BirdGenInputTypes::CrestHeight,
+                        0.0,
+                        40.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.crest_plumes = v,
+                        BirdGenInputTypes::CrestPlumes,
+                        1.0,
+                        9.0,
+                        5.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.crest_sweep = v,
+                        BirdGenInputTypes::CrestSweep,
+                        -30.0,
+                        90.0,
+                        35.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.crest_thickness = v,
+                        BirdGenInputTypes::CrestThickness,
+                        1.0,
+                        8.0,
+                        3.0
+                    ),
                     separator(),
                     // Body Section
                     section_header(asset_server, "Body"),
@@ -322,63 +443,660 @@ fn ui_root(asset_server: &AssetServer) -> impl Bundle {
                     ),
                     slider(
                         asset_server,
-                        |inputs, v| inputs.bottom_size = v,
-                        BirdGenInputTypes::BottomSize,
-                        5.0,
-                        50.0,
-                        27.5
+                        |inputs, v| inputs.bottom_size = v,
+                        BirdGenInputTypes::BottomSize,
+                        5.0,
+                        50.0,
+                        27.5
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::BodyStyle),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.neck_length = v,
+                        BirdGenInputTypes::NeckLength,
+                        0.0,
+                        40.0,
+                        10.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.belly_sag = v,
+                        BirdGenInputTypes::BellySag,
+                        -50.0,
+                        50.0,
+                        10.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.back_arch = v,
+                        BirdGenInputTypes::BackArch,
+                        -50.0,
+                        50.0,
+                        0.0
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::Geometry),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.blend_radius = v,
+                        BirdGenInputTypes::BlendRadius,
+                        0.0,
+                        30.0,
+                        8.0
+                    ),
+                    separator(),
+                    // Wings Section
+                    section_header(asset_server, "Wings"),
+                    option_button(asset_server, BirdGenOptionTypes::WingPose),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.wing_length = v,
+                        BirdGenInputTypes::WingLength,
+                        0.0,
+                        80.0,
+                        45.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.wing_width = v,
+                        BirdGenInputTypes::WingWidth,
+                        5.0,
+                        40.0,
+                        22.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.wing_droop = v,
+                        BirdGenInputTypes::WingDroop,
+                        -30.0,
+                        60.0,
+                        15.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.wing_spread = v,
+                        BirdGenInputTypes::WingSpread,
+                        0.0,
+                        90.0,
+                        10.0
+                    ),
+                    separator(),
+                    // Tail Section
+                    section_header(asset_server, "Tail"),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_length = v,
+                        BirdGenInputTypes::TailLength,
+                        0.0,
+                        100.0,
+                        50.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_width = v,
+                        BirdGenInputTypes::TailWidth,
+                        1.0,
+                        50.0,
+                        25.5
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_yaw = v,
+                        BirdGenInputTypes::TailYaw,
+                        -45.0,
+                        45.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_pitch = v,
+                        BirdGenInputTypes::TailPitch,
+                        -45.0,
+                        90.0,
+                        22.5
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_roundness = v,
+                        BirdGenInputTypes::TailRoundness,
+                        10.0,
+                        200.0,
+                        105.0
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::TailStyle),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_feathers = v,
+                        BirdGenInputTypes::TailFeathers,
+                        3.0,
+                        12.0,
+                        7.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_spread = v,
+                        BirdGenInputTypes::TailSpread,
+                        10.0,
+                        180.0,
+                        60.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_fork = v,
+                        BirdGenInputTypes::TailFork,
+                        10.0,
+                        90.0,
+                        50.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_point = v,
+                        BirdGenInputTypes::TailPoint,
+                        0.0,
+                        200.0,
+                        80.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.tail_cock = v,
+                        BirdGenInputTypes::TailCock,
+                        45.0,
+                        120.0,
+                        80.0
+                    ),
+                    separator(),
+                    // Markings Section
+                    section_header(asset_server, "Markings"),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.breast_patch = v,
+                        BirdGenInputTypes::BreastPatch,
+                        0.0,
+                        60.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.wing_bars = v,
+                        BirdGenInputTypes::WingBars,
+                        0.0,
+                        4.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.cheek_spots = v,
+                        BirdGenInputTypes::CheekSpots,
+                        0.0,
+                        15.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.face_mask = v,
+                        BirdGenInputTypes::FaceMask,
+                        0.0,
+                        15.0,
+                        0.0
+                    ),
+                    separator(),
+                    // Colours Section
+                    section_header(asset_server, "Colours"),
+                    option_button(asset_server, BirdGenOptionTypes::ColourPart),
+                    colour_swatch(),
+                    slider(
+                        asset_server,
+                        |inputs, v| part_colour_mut(inputs.colour_part, inputs).hue = v,
+                        BirdGenInputTypes::ColourHue,
+                        0.0,
+                        360.0,
+                        8.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| part_colour_mut(inputs.colour_part, inputs).saturation = v,
+                        BirdGenInputTypes::ColourSaturation,
+                        0.0,
+                        100.0,
+                        66.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| part_colour_mut(inputs.colour_part, inputs).lightness = v,
+                        BirdGenInputTypes::ColourLightness,
+                        0.0,
+                        100.0,
+                        50.0
+                    ),
+                    separator(),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.base_flat = v,
+                        BirdGenInputTypes::BaseFlat,
+                        -100.0,
+                        100.0,
+                        0.0
+                    ),
+                    separator(),
+                    // Stand Section
+                    section_header(asset_server, "Stand"),
+                    option_button(asset_server, BirdGenOptionTypes::Stand),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.leg_length = v,
+                        BirdGenInputTypes::LegLength,
+                        5.0,
+                        60.0,
+                        20.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.leg_thickness = v,
+                        BirdGenInputTypes::LegThickness,
+                        2.0,
+                        10.0,
+                        4.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.leg_spacing = v,
+                        BirdGenInputTypes::LegSpacing,
+                        4.0,
+                        40.0,
+                        14.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.toe_count = v,
+                        BirdGenInputTypes::ToeCount,
+                        1.0,
+                        5.0,
+                        3.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.toe_splay = v,
+                        BirdGenInputTypes::ToeSplay,
+                        0.0,
+                        120.0,
+                        60.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.perch_diameter = v,
+                        BirdGenInputTypes::PerchDiameter,
+                        4.0,
+                        20.0,
+                        8.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.perch_height = v,
+                        BirdGenInputTypes::PerchHeight,
+                        0.0,
+                        60.0,
+                        20.0
+                    ),
+                    separator(),
+                    // Name Plate Section
+                    section_header(asset_server, "Name Plate"),
+                    option_button(asset_server, BirdGenOptionTypes::NamePlate),
+                    name_text_field(asset_server),
+                    option_button(asset_server, BirdGenOptionTypes::NameStyle),
+                    option_button(asset_server, BirdGenOptionTypes::NameFont),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.name_size = v,
+                        BirdGenInputTypes::NameSize,
+                        3.0,
+                        20.0,
+                        8.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.name_depth = v,
+                        BirdGenInputTypes::NameDepth,
+                        0.2,
+                        3.0,
+                        1.0
+                    ),
+                    separator(),
+                    // Container Section
+                    section_header(asset_server, "Container"),
+                    option_button(asset_server, BirdGenOptionTypes::Container),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.container_wall = v,
+                        BirdGenInputTypes::ContainerWall,
+                        1.0,
+                        10.0,
+                        2.4
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.container_opening = v,
+                        BirdGenInputTypes::ContainerOpening,
+                        5.0,
+                        60.0,
+                        25.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.drainage_diameter = v,
+                        BirdGenInputTypes::DrainageDiameter,
+                        0.0,
+                        15.0,
+                        0.0
+                    ),
+                    separator(),
+                    // Whistle Section
+                    section_header(asset_server, "Whistle"),
+                    option_button(asset_server, BirdGenOptionTypes::Whistle),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.whistle_chamber = v,
+                        BirdGenInputTypes::WhistleChamber,
+                        10.0,
+                        60.0,
+                        35.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.windway_width = v,
+                        BirdGenInputTypes::WindwayWidth,
+                        3.0,
+                        10.0,
+                        6.0
+                    ),
+                    separator(),
+                    // Mounting Section
+                    section_header(asset_server, "Mounting"),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.loop_diameter = v,
+                        BirdGenInputTypes::LoopDiameter,
+                        0.0,
+                        20.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.loop_position = v,
+                        BirdGenInputTypes::LoopPosition,
+                        0.0,
+                        100.0,
+                        30.0
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::MagnetSize),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.screw_diameter = v,
+                        BirdGenInputTypes::ScrewDiameter,
+                        0.0,
+                        6.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.screw_depth = v,
+                        BirdGenInputTypes::ScrewDepth,
+                        2.0,
+                        20.0,
+                        6.0
+                    ),
+                    separator(),
+                    // Smoothing Section
+                    section_header(asset_server, "Smoothing"),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.subdivisions = v,
+                        BirdGenInputTypes::Subdivisions,
+                        0.0,
+                        2.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.smoothing_passes = v,
+                        BirdGenInputTypes::SmoothingPasses,
+                        0.0,
+                        20.0,
+                        0.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.smoothing_angle = v,
+                        BirdGenInputTypes::SmoothingAngle,
+                        0.0,
+                        90.0,
+                        0.0
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::LowPoly),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.low_poly_faces = v,
+                        BirdGenInputTypes::LowPolyFaces,
+                        100.0,
+                        5000.0,
+                        600.0
+                    ),
+                    separator(),
+                    // Blocks Section
+                    section_header(asset_server, "Blocks"),
+                    option_button(asset_server, BirdGenOptionTypes::Blocks),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.voxel_size = v,
+                        BirdGenInputTypes::VoxelSize,
+                        1.0,
+                        10.0,
+                        4.0
+                    ),
+                    separator(),
+                    // Outline Section
+                    section_header(asset_server, "Outline"),
+                    option_button(asset_server, BirdGenOptionTypes::OutlineView),
+                    option_button(asset_server, BirdGenOptionTypes::CookieCutter),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.cutter_wall = v,
+                        BirdGenInputTypes::CutterWall,
+                        0.4,
+                        4.0,
+                        1.2
+                    ),
+                    separator(),
+                    // Laser Cutting Section
+                    section_header(asset_server, "Laser Cutting"),
+                    option_button(asset_server, BirdGenOptionTypes::SliceAxis),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.sheet_thickness = v,
+                        BirdGenInputTypes::SheetThickness,
+                        1.0,
+                        10.0,
+                        3.0
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::Sliceform),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.slice_spacing = v,
+                        BirdGenInputTypes::SliceSpacing,
+                        5.0,
+                        40.0,
+                        12.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.sheet_width = v,
+                        BirdGenInputTypes::SheetWidth,
+                        100.0,
+                        1000.0,
+                        300.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.sheet_height = v,
+                        BirdGenInputTypes::SheetHeight,
+                        100.0,
+                        1000.0,
+                        300.0
+                    ),
+                    separator(),
+                    // Papercraft Section
+                    section_header(asset_server, "Papercraft"),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.paper_faces = v,
+                        BirdGenInputTypes::PaperFaces,
+                        50.0,
+                        800.0,
+                        200.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.paper_scale = v,
+                        BirdGenInputTypes::PaperScale,
+                        50.0,
+                        400.0,
+                        100.0
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::PaperSize),
+                    separator(),
+                    // Mould Section
+                    section_header(asset_server, "Mould"),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.mould_wall = v,
+                        BirdGenInputTypes::MouldWall,
+                        5.0,
+                        30.0,
+                        10.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.key_clearance = v,
+                        BirdGenInputTypes::KeyClearance,
+                        0.0,
+                        1.0,
+                        0.2
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.sprue_diameter = v,
+                        BirdGenInputTypes::SprueDiameter,
+                        3.0,
+                        15.0,
+                        6.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.vent_count = v,
+                        BirdGenInputTypes::VentCount,
+                        0.0,
+                        6.0,
+                        2.0
+                    ),
+                    separator(),
+                    // Relief Section
+                    section_header(asset_server, "Relief"),
+                    option_button(asset_server, BirdGenOptionTypes::Relief),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.relief_depth = v,
+                        BirdGenInputTypes::ReliefDepth,
+                        1.0,
+                        20.0,
+                        4.0
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::ReliefPlate),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.plate_thickness = v,
+                        BirdGenInputTypes::PlateThickness,
+                        0.8,
+                        5.0,
+                        2.0
+                    ),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.plate_border = v,
+                        BirdGenInputTypes::PlateBorder,
+                        0.0,
+                        10.0,
+                        2.0
+                    ),
+                    option_button(asset_server, BirdGenOptionTypes::JumpRing),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.jump_ring_diameter = v,
+                        BirdGenInputTypes::JumpRingDiameter,
+                        1.0,
+                        6.0,
+                        3.0
+                    ),
+                    separator(),
+                    // Printing Section
+                    section_header(asset_server, "Printing"),
+                    option_button(asset_server, BirdGenOptionTypes::SplitHead),
+                    slider(
+                        asset_server,
+                        |inputs, v| inputs.peg_diameter = v,
+                        BirdGenInputTypes::PegDiameter,
+                        2.0,
+                        15.0,
+                        6.0
                     ),
-                    separator(),
-                    // Tail Section
-                    section_header(asset_server, "Tail"),
                     slider(
                         asset_server,
-                        |inputs, v| inputs.tail_length = v,
-                        BirdGenInputTypes::TailLength,
-                        0.0,
-                        100.0,
-                        50.0
+                        |inputs, v| inputs.peg_length = v,
+                        BirdGenInputTypes::PegLength,
+                        2.0,
+                        20.0,
+                        8.0
                     ),
                     slider(
                         asset_server,
-                        |inputs, v| inputs.tail_width = v,
-                        BirdGenInputTypes::TailWidth,
+                        |inputs, v| inputs.peg_clearance = v,
+                        BirdGenInputTypes::PegClearance,
+                        0.0,
                         1.0,
-                        50.0,
-                        25.5
+                        0.2
                     ),
+                    separator(),
+                    // Print Estimate Section
+                    section_header(asset_server, "Print Estimate"),
+                    option_button(asset_server, BirdGenOptionTypes::FilamentDiameter),
                     slider(
                         asset_server,
-                        |inputs, v| inputs.tail_yaw = v,
-                        BirdGenInputTypes::TailYaw,
-                        -45.0,
-                        45.0,
-                        0.0
+                        |inputs, v| inputs.material_density = v,
+                        BirdGenInputTypes::MaterialDensity,
+                        0.8,
+                        2.0,
+                        1.24
                     ),
                     slider(
                         asset_server,
-                        |inputs, v| inputs.tail_pitch = v,
-                        BirdGenInputTypes::TailPitch,
-                        -45.0,
-                        90.0,
-                        22.5
+                        |inputs, v| inputs.layer_height = v,
+                        BirdGenInputTypes::LayerHeight,
+                        0.08,
+                        0.4,
+                        0.2
                     ),
                     slider(
                         asset_server,
-                        |inputs, v| inputs.tail_roundness = v,
-                        BirdGenInputTypes::TailRoundness,
+                        |inputs, v| inputs.print_speed = v,
+                        BirdGenInputTypes::PrintSpeed,
                         10.0,
                         200.0,
-                        105.0
+                        60.0
                     ),
-                    separator(),
                     slider(
                         asset_server,
-                        |inputs, v| inputs.base_flat = v,
-                        BirdGenInputTypes::BaseFlat,
-                        -100.0,
+                        |inputs, v| inputs.infill = v,
+                        BirdGenInputTypes::Infill,
+                        0.0,
                         100.0,
-                        0.0
+                        15.0
                     ),
                 ]
             ),
@@ -419,25 +1137,82 @@ fn ui_root(asset_server: &AssetServer) -> impl Bundle {
                                     bird_inputs.beak_size = rng.random_range(20.0..=100.0);
                                     bird_inputs.beak_width = rng.random_range(0.0..=25.0);
                                     bird_inputs.beak_roundness = rng.random_range(10.0..=200.0);
+                                    bird_inputs.beak_shape = [
+                                        BeakShape::Straight,
+                                        BeakShape::Hooked,
+                                        BeakShape::Curved,
+                                        BeakShape::Duck,
+                                    ][rng.random_range(0..4)];
+                                    bird_inputs.beak_curve = rng.random_range(-45.0..=90.0);
+                                    bird_inputs.beak_droop = rng.random_range(0.0..=100.0);
                                     bird_inputs.head_size = rng.random_range(10.0..=40.0);
                                     bird_inputs.head_to_belly = rng.random_range(-20.0..=50.0);
                                     bird_inputs.eye_size = rng.random_range(0.0..=20.0);
+                                    bird_inputs.eye_spacing = rng.random_range(0.0..=90.0);
+                                    bird_inputs.eye_angle = rng.random_range(-30.0..=90.0);
                                     bird_inputs.head_lateral_offset =
                                         rng.random_range(-15.0..=15.0);
                                     bird_inputs.head_level = rng.random_range(0.0..=80.0);
                                     bird_inputs.head_yaw = rng.random_range(-45.0..=45.0);
                                     bird_inputs.head_pitch = rng.random_range(-80.0..=45.0);
+                                    bird_inputs.crest_height = rng.random_range(0.0..=40.0);
+                                    bird_inputs.crest_plumes = rng.random_range(1.0..=9.0);
+                                    bird_inputs.crest_sweep = rng.random_range(-30.0..=90.0);
+                                    bird_inputs.crest_thickness = rng.random_range(1.0..=8.0);
                                     bird_inputs.belly_length = rng.random_range(10.0..=100.0);
                                     bird_inputs.belly_size = rng.random_range(20.0..=60.0);
                                     bird_inputs.belly_fat = rng.random_range(50.0..=150.0);
                                     bird_inputs.belly_to_bottom = rng.random_range(1.0..=50.0);
                                     bird_inputs.bottom_size = rng.random_range(5.0..=50.0);
+                                    // the SDF backend only does the hull body
+                                    bird_inputs.body_style = if bird_inputs.geometry
+                                        == GeometryBackend::Csg
+                                        && rng.random_bool(0.5)
+                                    {
+                                        BodyStyle::Lofted
+                                    } else {
+                                        BodyStyle::Hull
+                                    };
+                                    bird_inputs.neck_length = rng.random_range(0.0..=40.0);
+                                    bird_inputs.belly_sag = rng.random_range(-50.0..=50.0);
+                                    bird_inputs.back_arch = rng.random_range(-50.0..=50.0);
+                                    bird_inputs.wing_length = rng.random_range(0.0..=80.0);
+                                    bird_inputs.wing_width = rng.random_range(5.0..=40.0);
+                                    bird_inputs.wing_droop = rng.random_range(-30.0..=60.0);
+                                    bird_inputs.wing_spread = rng.random_range(0.0..=90.0);
+                                    bird_inputs.wing_pose = if rng.random_bool(0.5) {
+                                        WingPose::Folded
+                                    } else {
+                                        WingPose::Spread
+                                    };
                                     bird_inputs.tail_length = rng.random_range(0.0..=100.0);
                                     bird_inputs.tail_width = rng.random_range(1.0..=50.0);
                                     bird_inputs.tail_yaw = rng.random_range(-45.0..=45.0);
                                     bird_inputs.tail_pitch = rng.random_range(-45.0..=90.0);
                                     bird_inputs.tail_roundness = rng.random_range(10.0..=200.0);
+                                    bird_inputs.tail_style = [
+                                        TailStyle::Classic,
+                                        TailStyle::Forked,
+                                        TailStyle::Fan,
+                                        TailStyle::Pointed,
+                                        TailStyle::Wren,
+                                    ][rng.random_range(0..5)];
+                                    bird_inputs.tail_feathers = rng.random_range(3.0..=12.0);
+                                    bird_inputs.tail_spread = rng.random_range(10.0..=180.0);
+                                    bird_inputs.tail_fork = rng.random_range(10.0..=90.0);
+                                    bird_inputs.tail_point = rng.random_range(0.0..=200.0);
+                                    bird_inputs.tail_cock = rng.random_range(45.0..=120.0);
                                     bird_inputs.base_flat = rng.random_range(-100.0..=100.0);
+                                    bird_inputs.stand =
+                                        [BirdStand::Belly, BirdStand::Legs, BirdStand::Perch]
+                                            [rng.random_range(0..3)];
+                                    bird_inputs.leg_length = rng.random_range(5.0..=60.0);
+                                    bird_inputs.leg_thickness = rng.random_range(2.0..=10.0);
+                                    bird_inputs.leg_spacing = rng.random_range(4.0..=40.0);
+                                    bird_inputs.toe_count = rng.random_range(1.0..=5.0);
+                                    bird_inputs.toe_splay = rng.random_range(0.0..=120.0);
+                                    bird_inputs.perch_diameter = rng.random_range(4.0..=20.0);
+                                    bird_inputs.perch_height = rng.random_range(0.0..=60.0);
 
                                     rebuild_writer.write(RebuildBird);
                                 }
@@ -445,6 +1220,174 @@ fn ui_root(asset_server: &AssetServer) -> impl Bundle {
                         ),
                     ),
                     separator(),
+                    // Saving and exporting
+                    (
+                        action_button(asset_server, "Save Design"),
+                        observe(|_activate: On<Activate>, bird_inputs: Res<BirdGenInputs>| {
+                            save_design(&bird_inputs);
+                        }),
+                    ),
+                    (
+                        action_button(asset_server, "Load Design"),
+                        observe(
+                            |_activate: On<Activate>,
+                             mut bird_inputs: ResMut<BirdGenInputs>,
+                             mut rebuild_writer: MessageWriter<RebuildBird>,
+                             bird_state: Res<State<BirdState>>| {
+                                if *bird_state.get() == BirdState::BirdVisible
+                                    && let Some(saved_inputs) = load_design()
+                                {
+                                    *bird_inputs = saved_inputs;
+                                    rebuild_writer.write(RebuildBird);
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export 3MF"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                if let Some(bird) = bird {
+                                    save_file("bird.3mf", &bird_3mf(&bird.0, &bird_inputs));
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export STL"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                if let Some(bird) = bird {
+                                    for (file_name, stl) in bird_stls(&bird.0, &bird_inputs) {
+                                        save_file(&file_name, &stl);
+                                    }
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export GLB"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                if let Some(bird) = bird {
+                                    save_file("bird.glb", &bird_glb(&bird.0, &bird_inputs));
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export SVG"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                if let Some(bird) = bird {
+                                    let outline = bird_outline(&bird.0, &bird_inputs);
+                                    save_file("bird-outline.svg", outline_svg(&outline).as_bytes());
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export DXF"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                if let Some(bird) = bird {
+                                    let outline = bird_outline(&bird.0, &bird_inputs);
+                                    save_file("bird-outline.dxf", outline_dxf(&outline).as_bytes());
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export Slices"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                if let Some(bird) = bird {
+                                    for (index, sheet) in
+                                        bird_slice_sheets(&bird.0, &bird_inputs).iter().enumerate()
+                                    {
+                                        save_file(
+                                            &format!("bird-slices-{}.svg", index + 1),
+                                            sheet.as_bytes(),
+                                        );
+                                    }
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export Papercraft"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                if let Some(bird) = bird {
+                                    for (index, page) in
+                                        bird_papercraft_pages(&bird.0, &bird_inputs)
+                                            .iter()
+                                            .enumerate()
+                                    {
+                                        save_file(
+                                            &format!("bird-papercraft-{}.svg", index + 1),
+                                            page.as_bytes(),
+                                        );
+                                    }
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export Mould"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                if let Some(bird) = bird {
+                                    let [first, second] = bird_mould(&bird.0, &bird_inputs);
+                                    save_file("bird-mould-a.stl", &meshes_stl(&[&first]));
+                                    save_file("bird-mould-b.stl", &meshes_stl(&[&second]));
+                                }
+                            }
+                        ),
+                    ),
+                    (
+                        action_button(asset_server, "Export Blocks"),
+                        observe(
+                            |_activate: On<Activate>,
+                             bird: Option<Res<CurrentBird>>,
+                             bird_inputs: Res<BirdGenInputs>| {
+                                let Some(voxels) =
+                                    bird.as_ref().and_then(|bird| bird.0.voxels.as_ref())
+                                else {
+                                    info!("Turn on Blocks to export them");
+                                    return;
+                                };
+                                save_file("bird-voxels.json", voxels.json(&bird_inputs).as_bytes());
+                                save_file("bird-voxels.csv", voxels.csv(&bird_inputs).as_bytes());
+                                if voxels.bricks {
+                                    save_file(
+                                        "bird-bricks.csv",
+                                        voxels.brick_csv(&bird_inputs).as_bytes(),
+                                    );
+                                }
+                            }
+                        ),
+                    ),
+                    separator(),
+                    // Stats
+                    stats_text(asset_server),
+                    separator(),
                     // Footer
                     footer(asset_server),
                 ]
@@ -617,6 +1560,42 @@ where
     )
 }
 
+fn option_button(asset_server: &AssetServer, option_type: BirdGenOptionTypes) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.),
+            min_height: px(LINE_HEIGHT),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            margin: UiRect::vertical(px(5.)),
+            padding: UiRect::axes(px(8.), px(4)),
+            border: UiRect::all(px(2.)),
+            ..default()
+        },
+        Button,
+        BirdOptionButton { option_type },
+        Hovered::default(),
+        BackgroundColor(NORMAL_BUTTON),
+        BorderColor::all(Color::BLACK),
+        BorderRadius::all(px(5.)),
+        // label gets filled in by update_option_labels
+        children![(
+            Text::new(get_option_type_string(&option_type)),
+            TextFont {
+                font: asset_server.load("fonts/OTBrut-Regular.ttf"),
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        )],
+        observe(
+            move |_activate: On<Activate>, mut bird_inputs: ResMut<BirdGenInputs>| {
+                cycle_option_value(&option_type, &mut bird_inputs);
+            },
+        ),
+    )
+}
+
 fn regenerate_button(asset_server: &AssetServer) -> impl Bundle {
     (
         Node {
@@ -647,6 +1626,120 @@ fn regenerate_button(asset_server: &AssetServer) -> impl Bundle {
     )
 }
 
+fn action_button(asset_server: &AssetServer, label: &str) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.),
+            min_height: px(30.),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            margin: UiRect::vertical(px(3.)),
+            padding: UiRect::axes(px(8.), px(4)),
+            border: UiRect::all(px(2.)),
+            ..default()
+        },
+        Button,
+        ActionButton,
+        Hovered::default(),
+        BackgroundColor(NORMAL_BUTTON),
+        BorderColor::all(Color::BLACK),
+        BorderRadius::all(px(5.)),
+        children![(
+            Text::new(label),
+            TextFont {
+                font: asset_server.load("fonts/OTBrut-Regular.ttf"),
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        )],
+    )
+}
+
+// Click it, then type (enter or escape to stop typing)
+fn name_text_field(asset_server: &AssetServer) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.),
+            min_height: px(LINE_HEIGHT),
+            align_items: AlignItems::Center,
+            margin: UiRect::vertical(px(5.)),
+            padding: UiRect::axes(px(8.), px(4)),
+            border: UiRect::all(px(2.)),
+            ..default()
+        },
+        Button,
+        NameTextField,
+        BackgroundColor(SLIDER_TRACK),
+        BorderColor::all(Color::BLACK),
+        BorderRadius::all(px(5.)),
+        // text gets filled in by update_name_text_field
+        children![(
+            Text::new(""),
+            TextFont {
+                font: asset_server.load("fonts/OTBrut-Regular.ttf"),
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        )],
+        observe(
+            |activate: On<Activate>, mut input_focus: ResMut<InputFocus>| {
+                input_focus.0 = Some(activate.entity);
+            },
+        ),
+        observe(
+            |key_press: On<FocusedInput<KeyboardInput>>,
+             mut bird_inputs: ResMut<BirdGenInputs>,
+             mut input_focus: ResMut<InputFocus>| {
+                if !key_press.input.state.is_pressed() {
+                    return;
+                }
+                let room_left = bird_inputs.name_text.chars().count() < NAME_TEXT_MAX_LENGTH;
+                match &key_press.input.logical_key {
+                    Key::Backspace => {
+                        bird_inputs.name_text.pop();
+                    }
+                    Key::Enter | Key::Escape => input_focus.0 = None,
+                    Key::Space if room_left => bird_inputs.name_text.push(' '),
+                    Key::Character(typed) if room_left => bird_inputs.name_text.push_str(typed),
+                    _ => {}
+                }
+            },
+        ),
+    )
+}
+
+// Preview of the colour currently being edited
+fn colour_swatch() -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.),
+            height: px(20.),
+            margin: UiRect::vertical(px(5.)),
+            border: UiRect::all(px(2.)),
+            ..default()
+        },
+        BackgroundColor(NORMAL_BUTTON),
+        BorderColor::all(Color::BLACK),
+        BorderRadius::all(px(5.)),
+        BirdColourSwatch,
+    )
+}
+
+fn stats_text(asset_server: &AssetServer) -> impl Bundle {
+    (
+        Text::new(""),
+        TextFont {
+            font: asset_server.load("fonts/OTBrut-Regular.ttf"),
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(TEXT_COLOR),
+        BirdStatsText,
+    )
+}
+
 fn footer(asset_server: &AssetServer) -> impl Bundle {
     (
         Text::new("ported/inspired by bird-o-matic by mooncactus"),
@@ -673,7 +1766,12 @@ fn update_button_style(
         ),
         (
             Or<(Changed<Hovered>, Added<InteractionDisabled>)>,
-            Or<(With<RegenerateButton>, With<RandomizeButton>)>,
+            Or<(
+                With<RegenerateButton>,
+                With<RandomizeButton>,
+                With<ActionButton>,
+                With<BirdOptionButton>,
+            )>,
         ),
     >,
 ) {
@@ -690,7 +1788,12 @@ fn update_button_style2(
             &mut BackgroundColor,
             &mut BorderColor,
         ),
-        Or<(With<RegenerateButton>, With<RandomizeButton>)>,
+        Or<(
+            With<RegenerateButton>,
+            With<RandomizeButton>,
+            With<ActionButton>,
+            With<BirdOptionButton>,
+        )>,
     >,
     mut removed_disabled: RemovedComponents<InteractionDisabled>,
 ) {
@@ -803,6 +1906,90 @@ fn update_slider_values(
     }
 }
 
+fn update_option_labels(
+    res: Res<BirdGenInputs>,
+    buttons: Query<(&BirdOptionButton, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    if res.is_changed() {
+        for (option_button, button_children) in buttons.iter() {
+            for child in button_children.iter() {
+                if let Ok(mut text) = texts.get_mut(child) {
+                    text.0 = format!(
+                        "{}: {}",
+                        get_option_type_string(&option_button.option_type),
+                        get_option_value_string(&option_button.option_type, &res)
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn update_colour_swatch(
+    res: Res<BirdGenInputs>,
+    mut swatches: Query<&mut BackgroundColor, With<BirdColourSwatch>>,
+) {
+    if res.is_changed() {
+        for mut swatch_color in swatches.iter_mut() {
+            *swatch_color = part_colour(res.colour_part, &res).to_color().into();
+        }
+    }
+}
+
+fn update_name_text_field(
+    res: Res<BirdGenInputs>,
+    input_focus: Res<InputFocus>,
+    fields: Query<(Entity, &Children), With<NameTextField>>,
+    mut texts: Query<&mut Text>,
+) {
+    if !res.is_changed() && !input_focus.is_changed() {
+        return;
+    }
+    for (field, field_children) in fields.iter() {
+        let typing = input_focus.0 == Some(field);
+        for child in field_children.iter() {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.0 = if typing {
+                    format!("{}_", res.name_text)
+                } else if res.name_text.is_empty() {
+                    "Click to type a name".to_string()
+                } else {
+                    res.name_text.clone()
+                };
+            }
+        }
+    }
+}
+
+// Print settings get applied right away, the mesh stats only change when the bird is rebuilt
+fn update_stats_text(
+    stats: Res<BirdStats>,
+    inputs: Res<BirdGenInputs>,
+    mut texts: Query<&mut Text, With<BirdStatsText>>,
+) {
+    if !stats.is_changed() && !inputs.is_changed() {
+        return;
+    }
+    let mesh_stats = &stats.mesh;
+    let estimate = PrintEstimate::from_mesh_stats(mesh_stats, &inputs);
+    let size = mesh_stats.size();
+    for mut text in texts.iter_mut() {
+        text.0 = format!(
+            "Triangles: {}\nVolume: {:.1} cm3\nSurface: {:.1} cm2\nSize: {:.0} x {:.0} x {:.0} mm\nFilament: {:.1} g / {:.2} m\nPrint time: ~{}",
+            mesh_stats.triangle_count,
+            mesh_stats.volume / 1000.0,
+            mesh_stats.surface_area / 100.0,
+            size.x,
+            size.y,
+            size.z,
+            estimate.filament_grams,
+            estimate.filament_length / 1000.0,
+            format_print_time(estimate.print_minutes),
+        );
+    }
+}
+
 fn thumb_color(disabled: bool, hovered: bool) -> Color {
     match (disabled, hovered) {
         (true, _) => Color::srgb(0.5, 0.5, 0.5),
@@ -0,0 +1,358 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy::{
+    color::ColorToPacked,
+    math::{DVec2, DVec3},
+};
+use csgrs::{
+    mesh::{polygon::Polygon, vertex::Vertex},
+    traits::CSG,
+};
+use serde_json::json;
+
+use crate::{
+    bird::{BirdGenInputs, BirdPart, BirdParts, BlockStyle, CSGMesh, part_colour},
+    stats::mesh_triangles,
+};
+
+// Bricks are a bit taller than they are wide (9.6mm high on an 8mm stud pitch)
+const BRICK_HEIGHT_RATIO: f64 = 1.2;
+const STUD_RADIUS_RATIO: f64 = 0.3;
+const STUD_HEIGHT_RATIO: f64 = 0.2;
+const STUD_SEGMENTS: usize = 16;
+// Rays get nudged off the cell centres a tiny bit, so they don't run exactly along triangle edges
+const RAY_NUDGE: DVec2 = DVec2::new(1.3e-5, 0.7e-5);
+// Brick footprints to fill each layer with, biggest first
+const BRICK_SIZES: [(i32, i32); 12] = [
+    (2, 4),
+    (4, 2),
+    (2, 3),
+    (3, 2),
+    (2, 2),
+    (1, 4),
+    (4, 1),
+    (1, 3),
+    (3, 1),
+    (1, 2),
+    (2, 1),
+    (1, 1),
+];
+
+// The bird as a grid of blocks, each one the colour of the part it came from
+pub struct VoxelBird {
+    pub size: f64,
+    pub layer_height: f64,
+    pub bricks: bool,
+    // grid cell (x, y, z) to the part filling it, cell x covers x * size to (x + 1) * size
+    pub cells: BTreeMap<[i32; 3], BirdPart>,
+}
+
+pub fn voxelize(parts: &BirdParts, input: &BirdGenInputs) -> VoxelBird {
+    let size = input.voxel_size as f64;
+    let bricks = input.blocks == BlockStyle::Bricks;
+    let layer_height = if bricks {
+        size * BRICK_HEIGHT_RATIO
+    } else {
+        size
+    };
+
+    let mut cells = BTreeMap::new();
+    // markings come after the part they're painted on, so they win where the two share a cell
+    for (part, mesh) in parts.iter() {
+        for cell in filled_cells(mesh, size, layer_height) {
+            cells.insert(cell, part);
+        }
+    }
+    VoxelBird {
+        size,
+        layer_height,
+        bricks,
+        cells,
+    }
+}
+
+// Cells whose centre is inside the mesh. A ray goes straight up through every column of cell
+// centres, and counting which way the triangles it passes through face (the winding number)
+// says whether each centre is inside.
+fn filled_cells(mesh: &CSGMesh, size: f64, layer_height: f64) -> Vec<[i32; 3]> {
+    let column_centre =
+        |x: i32, y: i32| DVec2::new(x as f64 + 0.5, y as f64 + 0.5) * size + RAY_NUDGE;
+
+    // every column's crossings, as (height, +1 going in or -1 coming out)
+    let mut columns: HashMap<(i32, i32), Vec<(f64, i32)>> = HashMap::new();
+    for [a, b, c] in mesh_triangles(mesh) {
+        let area = (b - a).truncate().perp_dot((c - a).truncate());
+        // standing on its edge, the rays slide past it
+        if area.abs() < 1e-12 {
+            continue;
+        }
+        // facing down is where a ray going up enters the solid
+        let crossing = if area < 0.0 { 1 } else { -1 };
+        let min = a.min(b).min(c);
+        let max = a.max(b).max(c);
+        for x in (min.x / size).floor() as i32..=(max.x / size).floor() as i32 {
+            for y in (min.y / size).floor() as i32..=(max.y / size).floor() as i32 {
+                let point = column_centre(x, y);
+                let weight_b = (point - a.truncate()).perp_dot((c - a).truncate()) / area;
+                let weight_c = (b - a).truncate().perp_dot(point - a.truncate()) / area;
+                let weight_a = 1.0 - weight_b - weight_c;
+                if weight_a < 0.0 || weight_b < 0.0 || weight_c < 0.0 {
+                    continue;
+                }
+                let height = a.z * weight_a + b.z * weight_b + c.z * weight_c;
+                columns.entry((x, y)).or_default().push((height, crossing));
+            }
+        }
+    }
+
+    let mut cells = Vec::new();
+    for ((x, y), mut crossings) in columns {
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let bottom = (crossings[0].0 / layer_height).floor() as i32;
+        let top = (crossings[crossings.len() - 1].0 / layer_height).ceil() as i32;
+        let mut winding = 0;
+        let mut next = 0;
+        for z in bottom..top {
+            let centre = (z as f64 + 0.5) * layer_height;
+            while next < crossings.len() && crossings[next].0 < centre {
+                winding += crossings[next].1;
+                next += 1;
+            }
+            if winding > 0 {
+                cells.push([x, y, z]);
+            }
+        }
+    }
+    cells
+}
+
+impl VoxelBird {
+    fn cell_corner(&self, cell: [i32; 3]) -> DVec3 {
+        DVec3::new(cell[0] as f64, cell[1] as f64, cell[2] as f64) * self.cell_extent()
+    }
+
+    fn cell_extent(&self) -> DVec3 {
+        DVec3::new(self.size, self.size, self.layer_height)
+    }
+
+    // Block mesh of one part. Faces only go where the neighbouring cell isn't the same part,
+    // so each part is still closed where it meets another. Bricks get a stud on every top
+    // that's showing.
+    pub fn part_mesh(&self, part: BirdPart) -> Option<CSGMesh> {
+        let mut polygons: Vec<Polygon<()>> = Vec::new();
+        for (cell, _) in self
+            .cells
+            .iter()
+            .filter(|(_, cell_part)| **cell_part == part)
+        {
+            for axis in 0..3 {
+                for side in [-1, 1] {
+                    let mut neighbour = *cell;
+                    neighbour[axis] += side;
+                    if self.cells.get(&neighbour) != Some(&part) {
+                        polygons.push(self.cell_face(*cell, axis, side));
+                    }
+                }
+            }
+            if self.bricks && !self.cells.contains_key(&[cell[0], cell[1], cell[2] + 1]) {
+                polygons.extend(self.stud(*cell).polygons);
+            }
+        }
+        if polygons.is_empty() {
+            return None;
+        }
+        Some(CSGMesh::from_polygons(&polygons, None))
+    }
+
+    // The square on one side of a cell, wound to face out
+    fn cell_face(&self, cell: [i32; 3], axis: usize, side: i32) -> Polygon<()> {
+        let corner = self.cell_corner(cell);
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        let mut normal = DVec3::ZERO;
+        normal[axis] = side as f64;
+        // going round u then v faces along +axis
+        let mut corners: Vec<DVec3> = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .iter()
+            .map(|(along_u, along_v)| {
+                let mut offset = DVec3::ZERO;
+                offset[axis] = if side > 0 { 1.0 } else { 0.0 };
+                offset[u] = *along_u;
+                offset[v] = *along_v;
+                corner + offset * self.cell_extent()
+            })
+            .collect();
+        if side < 0 {
+            corners.reverse();
+        }
+        Polygon::new(
+            corners
+                .iter()
+                .map(|corner| {
+                    Vertex::new(
+                        [corner.x, corner.y, corner.z].into(),
+                        [normal.x, normal.y, normal.z].into(),
+                    )
+                })
+                .collect(),
+            None,
+        )
+    }
+
+    // Stud on top of a brick, sunk into it a little so it's one solid once the slicer merges them
+    fn stud(&self, cell: [i32; 3]) -> CSGMesh {
+        let centre = self.cell_corner(cell) + self.cell_extent() * DVec3::new(0.5, 0.5, 1.0);
+        let height = self.size * STUD_HEIGHT_RATIO;
+        CSGMesh::cylinder(
+            self.size * STUD_RADIUS_RATIO,
+            height * 1.5,
+            STUD_SEGMENTS,
+            None,
+        )
+        .translate(centre.x, centre.y, centre.z - height * 0.5)
+    }
+
+    // How many blocks of each part
+    pub fn part_counts(&self) -> Vec<(BirdPart, usize)> {
+        let mut counts: Vec<(BirdPart, usize)> = Vec::new();
+        for part in self.cells.values() {
+            match counts.iter_mut().find(|(counted, _)| counted == part) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((*part, 1)),
+            }
+        }
+        counts
+    }
+
+    // Shopping list of bricks: every layer gets filled greedily with the biggest brick that fits,
+    // only joining up cells of the same part (colour). Gives (footprint, part, how many).
+    pub fn brick_list(&self) -> Vec<(String, BirdPart, usize)> {
+        let mut counts: Vec<(String, BirdPart, usize)> = Vec::new();
+        let mut used: HashSet<[i32; 3]> = HashSet::new();
+        // cells come in x then y order, so each new brick starts from its lowest corner
+        for (cell, part) in self.cells.iter() {
+            if used.contains(cell) {
+                continue;
+            }
+            let covered = |(width, length): (i32, i32)| {
+                (0..width).flat_map(move |dx| {
+                    (0..length).map(move |dy| [cell[0] + dx, cell[1] + dy, cell[2]])
+                })
+            };
+            let (width, length) = BRICK_SIZES
+                .iter()
+                .copied()
+                .find(|footprint| {
+                    covered(*footprint)
+                        .all(|other| self.cells.get(&other) == Some(part) && !used.contains(&other))
+                })
+                .unwrap_or((1, 1));
+            used.extend(covered((width, length)));
+
+            let footprint = format!("{}x{}", width.min(length), width.max(length));
+            match counts
+                .iter_mut()
+                .find(|(counted, counted_part, _)| *counted == footprint && counted_part == part)
+            {
+                Some((_, _, count)) => *count += 1,
+                None => counts.push((footprint, *part, 1)),
+            }
+        }
+        counts.sort_by(|a, b| a.0.cmp(&b.0));
+        counts
+    }
+
+    // Cell positions counted up from the lowest corner, which is what block games want
+    fn grid_cells(&self) -> impl Iterator<Item = ([i32; 3], BirdPart)> {
+        let lowest = self.cells.keys().fold([i32::MAX; 3], |lowest, cell| {
+            std::array::from_fn(|axis| lowest[axis].min(cell[axis]))
+        });
+        self.cells
+            .iter()
+            .map(move |(cell, part)| (std::array::from_fn(|axis| cell[axis] - lowest[axis]), *part))
+    }
+
+    // Voxel list, with the colours and a parts list
+    pub fn json(&self, input: &BirdGenInputs) -> String {
+        let voxels: Vec<_> = self
+            .grid_cells()
+            .map(|([x, y, z], part)| {
+                json!({
+                    "x": x,
+                    "y": y,
+                    "z": z,
+                    "part": part.name(),
+                    "colour": hex_colour(part, input),
+                })
+            })
+            .collect();
+        let parts: Vec<_> = self
+            .part_counts()
+            .iter()
+            .map(|(part, count)| {
+                json!({
+                    "part": part.name(),
+                    "colour": hex_colour(*part, input),
+                    "count": count,
+                })
+            })
+            .collect();
+        let mut voxel_json = json!({
+            "voxel_size": self.size,
+            "layer_height": self.layer_height,
+            "style": if self.bricks { "bricks" } else { "voxels" },
+            "parts": parts,
+            "voxels": voxels,
+        });
+        if self.bricks {
+            voxel_json["bricks"] = self
+                .brick_list()
+                .iter()
+                .map(|(footprint, part, count)| {
+                    json!({
+                        "size": footprint,
+                        "part": part.name(),
+                        "colour": hex_colour(*part, input),
+                        "count": count,
+                    })
+                })
+                .collect();
+        }
+        serde_json::to_string_pretty(&voxel_json).unwrap_or_default()
+    }
+
+    // Same voxel list, one row per block
+    pub fn csv(&self, input: &BirdGenInputs) -> String {
+        let mut csv = String::from("x,y,z,part,colour\n");
+        for ([x, y, z], part) in self.grid_cells() {
+            csv += &format!(
+                "{},{},{},{},{}\n",
+                x,
+                y,
+                z,
+                part.name(),
+                hex_colour(part, input)
+            );
+        }
+        csv
+    }
+
+    // The brick shopping list on its own
+    pub fn brick_csv(&self, input: &BirdGenInputs) -> String {
+        let mut csv = String::from("size,part,colour,count\n");
+        for (footprint, part, count) in self.brick_list() {
+            csv += &format!(
+                "{},{},{},{}\n",
+                footprint,
+                part.name(),
+                hex_colour(part, input),
+                count
+            );
+        }
+        csv
+    }
+}
+
+fn hex_colour(part: BirdPart, input: &BirdGenInputs) -> String {
+    let [r, g, b, _] = part_colour(part, input).to_color().to_srgba().to_u8_array();
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}